@@ -0,0 +1,1950 @@
+use serde::{Deserialize, Serialize};
+use std::f64::consts::TAU;
+
+//function whose roots are the pitch angles for targetting
+fn angle_check(x: f64, y: f64, u: f64, v: f64, a: f64, g: f64) -> f64 {
+    let p: f64 = (x*u)/(v*a.cos());
+    (u*u*x*(a.tan()))/g + p - (y*u*u)/g + (1.0-p).ln()
+}
+
+//Find critical point of angle_check through the regula falsi method to get the initial guess for root-finding and selecting direct and indirect shot pitch angles
+//Should be able to optimize it better, or use an external math crate if it becomes a problem
+//Capped so a stalled iteration reports NoConvergence instead of freezing the UI
+//The seeds atan(g x/v²) and its mirror scale with gravity, so nothing here assumes g = 10 (mortar stones use 5)
+const MAX_CRITICAL_POINT_ITERATIONS: u32 = 1000;
+
+pub fn find_critical_point(x: f64, u: f64, v: f64, g: f64) -> Result<f64, SolveError> {
+    let mut a: f64 = (g*x).atan2(v*v);
+    let mut b: f64 = (g*x).atan2(-v*v);
+
+    for _ in 0..MAX_CRITICAL_POINT_ITERATIONS {
+        let fa = g*x*a.sin() + u*v*x - v*v*a.cos();
+        let fb = g*x*b.sin() + u*v*x - v*v*b.cos();
+        //Equal ends (e.g. no velocity) leave no secant to follow
+        if fa == fb {
+            return Err(SolveError::NoConvergence);
+        }
+
+        let c = b - (fb * (b - a)) / (fb - fa);
+        
+        let fc = g*x*c.sin() + u*v*x - v*v*c.cos();
+        if fc.abs() < 0.00001 {
+            return Ok(c);
+        } else if fc.signum() == fa.signum() {
+            a = c;
+        } else {
+            b = c;
+        }
+    }
+
+    Err(SolveError::NoConvergence)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SolveError {
+    OutOfRange,
+    NoConvergence,
+    InvalidInput(InputError),
+}
+
+//Which input kept the solver from running, so the message can say what to fix
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputError {
+    Target,
+    Charges,
+    Ammo,
+    SamePosition,
+}
+
+impl std::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            SolveError::OutOfRange    => "OUT OF RANGE",
+            SolveError::NoConvergence => "NO CONVERGENCE",
+            SolveError::InvalidInput(InputError::Target)       => "Enter a valid target position",
+            SolveError::InvalidInput(InputError::Charges)      => "Enter at least 1 charge",
+            SolveError::InvalidInput(InputError::Ammo)         => "Enter a non-negative drag and a positive gravity",
+            SolveError::InvalidInput(InputError::SamePosition) => "Target equals cannon",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+//Half-typed fields parse to NaN and no charges give no velocity, neither of which the root-finders can handle
+//Negative drag or gravity from a custom ammo profile or a zero gravity multiplier would also keep them from converging
+pub fn check_input(d: f64, y: f64, u: f64, v: f64, g: f64) -> Result<(), SolveError> {
+    if !(d.is_finite() && y.is_finite() && d >= 0.0) {
+        Err(SolveError::InvalidInput(InputError::Target))
+    } else if !(v.is_finite() && v > 0.0) {
+        Err(SolveError::InvalidInput(InputError::Charges))
+    } else if !(u.is_finite() && g.is_finite() && u >= 0.0 && g > 0.0) {
+        Err(SolveError::InvalidInput(InputError::Ammo))
+    } else {
+        Ok(())
+    }
+}
+
+//Whether p = x*u/(v*cos(a)) has reached 1 at pitch a, where ln(1 - p) in angle_check is -infinity or NaN
+//p is smallest firing flat, so past x = v/u drag stops the projectile short of the target at any pitch
+fn beyond_drag_limit(x: f64, u: f64, v: f64, a: f64) -> bool {
+    x*u >= v*a.cos()
+}
+
+//Slope of angle_check with respect to the pitch, using dp/da = p*tan(a)
+fn angle_check_slope(x: f64, u: f64, v: f64, a: f64, g: f64) -> f64 {
+    let p: f64 = (x*u)/(v*a.cos());
+    (u*u*x)/(g*a.cos()*a.cos()) - p*p*a.tan()/(1.0 - p)
+}
+
+//Newton's method only goes wrong near the singularity where 1 - p reaches 0, so it's tried first inside the bracket
+//Starts from b, the end the stepping search in find_angles left within 0.1° of the root
+//None as soon as a step leaves the bracket or the slope flattens out, the bisection takes over then
+//Returns the root and the steps it took
+const MAX_NEWTON_STEPS: u32 = 10;
+
+fn newton_root(x: f64, y: f64, u: f64, v: f64, g: f64, a: f64, b: f64) -> Option<(f64, u32)> {
+    let (lo, hi) = if a < b { (a, b) } else { (b, a) };
+    let mut c: f64 = b;
+    for step in 0..MAX_NEWTON_STEPS {
+        let fc = angle_check(x, y, u, v, c, g);
+        if fc.abs() < 1e-12 {
+            return Some((c, step));
+        }
+        let slope = angle_check_slope(x, u, v, c, g);
+        if slope.is_nan() || slope.abs() < 1e-9 {
+            return None;
+        }
+        c -= fc / slope;
+        if c.is_nan() || c <= lo || c >= hi {
+            return None;
+        }
+    }
+    None
+}
+
+//Use bisection to find the roots of angle_check when the Newton fast path gives up
+//The critical point and a stepping search from ±90° give a bracket with a sign change on each side, so convergence is guaranteed
+//When only one side brackets a root (e.g. the other arc would need a pitch past ±90°) that arc alone is returned
+//The brackets are fixed angles and gravity only enters through angle_check, so they hold for any g > 0
+const MAX_BISECTION_ITERATIONS: u32 = 200;
+
+//Root of angle_check between a and b with the number of halvings it took, None when it runs out of iterations
+fn bisection_root(x: f64, y: f64, u: f64, v: f64, g: f64, a: f64, b: f64) -> Option<(f64, u32)> {
+    let (mut a, mut b) = (a, b);
+    let fa = angle_check(x, y, u, v, a, g);
+    for step in 1..=MAX_BISECTION_ITERATIONS {
+        let c: f64 = (a + b) / 2.0;
+        let fc = angle_check(x, y, u, v, c, g);
+        if fc.abs() < 1e-12 || c == a || c == b {
+            return Some((c, step));
+        } else if fc.signum() == fa.signum() {
+            a = c;
+        } else {
+            b = c;
+        }
+    }
+    None
+}
+
+//The stepping search in find_angles moves 0.1° at a time and never leaves ±90°, past vertical the model is meaningless
+const PITCH_SEARCH_STEP: f64 = 0.0017453292519943296;
+const PITCH_LIMIT: f64 = TAU/4.0;
+
+//How the solver got to its answer, for reporting odd solutions
+//iterations counts the Newton and bisection steps over both arcs, newton_iterations the Newton ones among them
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SolveInfo {
+    pub iterations: u32,
+    pub newton_iterations: u32,
+    pub method: &'static str
+}
+
+pub fn find_angles(x: f64, y: f64, u: f64, v: f64, g: f64, critical_point: f64) -> Result<(f64, f64, SolveInfo), SolveError>{
+    //angle_check degenerates without drag, but then the trajectory is a plain parabola
+    if u == 0.0 {
+        let (direct, indirect) = find_vacuum_angles(x, y, v, g)?;
+        return Ok((direct, indirect, SolveInfo { iterations: 0, newton_iterations: 0, method: "Closed form (no drag)" }));
+    }
+
+    let mut angles: [f64; 2] = [0.0, 0.0];
+    let mut error = SolveError::OutOfRange;
+    let mut info = SolveInfo { iterations: 0, newton_iterations: 0, method: "Newton" };
+    
+    //Ruled out before angle_check is evaluated, so the log never sees an argument of 0 or less
+    if beyond_drag_limit(x, u, v, 0.0) || beyond_drag_limit(x, u, v, critical_point) {
+        return Err(SolveError::OutOfRange);
+    }
+    if critical_point.is_nan() || critical_point.abs() >= PITCH_LIMIT {
+        return Err(SolveError::OutOfRange);
+    }
+    let cpa = angle_check(x, y, u, v, critical_point, g);
+    if cpa.is_nan() || cpa < 0.0 {
+        return Err(SolveError::OutOfRange);
+    } else if cpa < 1e-12 {
+        //Exactly at max range both arcs are the shot at the critical point
+        return Ok((critical_point, critical_point, SolveInfo { iterations: 0, newton_iterations: 0, method: "Critical point (equal roots)" }));
+    }
+    
+    for i in 0..2 {
+        let a: f64 = critical_point;
+
+        //One step inside ±90°, so b starts where the trajectory is still defined
+        let mut b: f64 = if i == 1 { PITCH_LIMIT - PITCH_SEARCH_STEP } else { -PITCH_LIMIT + PITCH_SEARCH_STEP };
+        
+        //Step towards the critical point until angle_check goes negative, leaving this arc as NaN once it's reached
+        let mut bracketed = true;
+        loop {
+            let fb = angle_check(x, y, u, v, b, g);
+            if fb < 0.0 { break }
+            if i == 0 { b += PITCH_SEARCH_STEP; }
+            else { b -= PITCH_SEARCH_STEP; }
+            if (i == 0 && b >= critical_point) || (i == 1 && b <= critical_point) || b.abs() >= PITCH_LIMIT {
+                bracketed = false;
+                break
+            }
+        }
+        if !bracketed {
+            angles[i] = f64::NAN;
+            continue;
+        }
+
+        if let Some((root, steps)) = newton_root(x, y, u, v, g, a, b) {
+            angles[i] = root;
+            info.iterations += steps;
+            info.newton_iterations += steps;
+            continue;
+        }
+
+        info.method = "Bisection fallback";
+        match bisection_root(x, y, u, v, g, a, b) {
+            Some((root, steps)) => {
+                angles[i] = root;
+                info.iterations += steps;
+            }
+            None => {
+                error = SolveError::NoConvergence;
+                angles[i] = f64::NAN;
+                info.iterations += MAX_BISECTION_ITERATIONS;
+            }
+        }
+    }
+
+    //A single arc is still a solution, the missing one is left as NaN
+    if angles[0].is_nan() && angles[1].is_nan() {
+        return Err(error);
+    }
+    Ok((angles[0], angles[1], info))
+}
+
+//Closed form pitch angles without drag: tan(a) = (v² ± sqrt(v⁴ - g(g x² + 2 y v²)))/(g x)
+//The low root is the direct shot and the high root the indirect one
+fn find_vacuum_angles(x: f64, y: f64, v: f64, g: f64) -> Result<(f64, f64), SolveError> {
+    let discriminant: f64 = v.powi(4) - g*(g*x*x + 2.0*y*v*v);
+    if discriminant < 0.0 {
+        return Err(SolveError::OutOfRange);
+    }
+    let direct: f64 = ((v*v - discriminant.sqrt())/(g*x)).atan();
+    let indirect: f64 = ((v*v + discriminant.sqrt())/(g*x)).atan();
+    Ok((direct, indirect))
+}
+
+//Height of the trajectory at horizontal distance x, the y(x) whose difference to the target angle_check scales
+pub fn trajectory_height(x: f64, u: f64, v: f64, g: f64, a: f64) -> f64 {
+    if u == 0.0 {
+        return x*a.tan() - (g*x*x)/(2.0*v*v*a.cos()*a.cos());
+    }
+    let p: f64 = (x*u)/(v*a.cos());
+    x*a.tan() + (g*p)/(u*u) + (g*(1.0-p).ln())/(u*u)
+}
+
+//Horizontal distance of the top of the arc, where vertical velocity reaches zero at x = v² sin(a) cos(a)/(g + u v sin(a))
+//Shots fired level or downwards only descend, so theirs is at the cannon
+fn apex_distance(u: f64, v: f64, g: f64, a: f64) -> f64 {
+    if a <= 0.0 {
+        return 0.0;
+    }
+    (v*v*a.sin()*a.cos())/(g + u*v*a.sin())
+}
+
+//Highest point of the trajectory on its way to the target, relative to the cannon
+//Past the target the path ends at the target instead
+pub fn apex_height(x: f64, u: f64, v: f64, g: f64, a: f64) -> f64 {
+    if a <= 0.0 {
+        return 0.0;
+    }
+    trajectory_height(apex_distance(u, v, g, a).min(x), u, v, g, a)
+}
+
+//Horizontal distance where a shot at pitch a passes height y, on the climb when rising and on the way down otherwise
+//None when the arc never gets that high, the height is monotonic on each side of the apex so bisection finds it
+pub fn pass_distance(y: f64, u: f64, v: f64, g: f64, a: f64, rising: bool) -> Option<f64> {
+    let apex_x: f64 = apex_distance(u, v, g, a);
+    if trajectory_height(apex_x, u, v, g, a) < y {
+        return None;
+    }
+    let (mut low, mut high): (f64, f64) = if rising {
+        if y < 0.0 {
+            return None;
+        }
+        (0.0, apex_x)
+    } else {
+        //With drag the path drops towards -infinity at the asymptote, without it the end is pushed out until it's below y
+        let mut high: f64 = if u == 0.0 { apex_x.max(1.0) } else { v*a.cos()/u };
+        while u == 0.0 && trajectory_height(high, u, v, g, a) > y {
+            high *= 2.0;
+        }
+        (apex_x, high)
+    };
+    for _ in 0..MAX_BISECTION_ITERATIONS {
+        let mid: f64 = (low + high)/2.0;
+        if mid == low || mid == high { break }
+        if (trajectory_height(mid, u, v, g, a) < y) == rising { low = mid } else { high = mid }
+    }
+    Some((low + high)/2.0)
+}
+
+//Where a shot fired at the fixed pitch a comes down through height y, the inverse of find_angles
+//None for pitches at or past ±90°, invalid inputs, or when the arc never gets as high as y
+pub fn landing_distance(y: f64, u: f64, v: f64, g: f64, a: f64) -> Option<f64> {
+    if check_input(0.0, y, u, v, g).is_err() || !a.is_finite() || a.abs() >= PITCH_LIMIT {
+        return None;
+    }
+    pass_distance(y, u, v, g, a, false)
+}
+
+//How far a shot at the achievable pitch b misses one at the exact pitch a aimed at distance x: the range error at the
+//height the exact shot arrives at, and the height error at x, both in blocks and positive when it goes long or high
+//The range error is NaN when the achievable arc doesn't reach that height at all
+pub fn aim_error(x: f64, u: f64, v: f64, g: f64, a: f64, b: f64) -> (f64, f64) {
+    let y: f64 = trajectory_height(x, u, v, g, a);
+    let rising: bool = x < apex_distance(u, v, g, a);
+    let range: f64 = pass_distance(y, u, v, g, b, rising).map_or(f64::NAN, |distance| distance - x);
+    (range, trajectory_height(x, u, v, g, b) - y)
+}
+
+//Sample the trajectory from the cannon to the target, as (horizontal distance, height) pairs for plotting
+const TRAJECTORY_SAMPLES: usize = 100;
+
+pub fn trajectory_points(x: f64, u: f64, v: f64, g: f64, a: f64) -> Vec<[f64; 2]> {
+    (0..=TRAJECTORY_SAMPLES)
+        .map(|i| {
+            let xi: f64 = x * i as f64 / TRAJECTORY_SAMPLES as f64;
+            [xi, trajectory_height(xi, u, v, g, a)]
+        })
+        .collect()
+}
+
+//Horizontal distance below which the target counts as straight above or below the cannon
+pub const VERTICAL_TOLERANCE: f64 = 1e-6;
+
+//Height after t seconds of a shot at pitch a, the vertical half of the motion on its own
+fn vertical_height(u: f64, v: f64, g: f64, a: f64, t: f64) -> f64 {
+    if u == 0.0 {
+        return v*a.sin()*t - g*t*t/2.0;
+    }
+    (v*a.sin() + g/u)*(1.0 - (-u*t).exp())/u - g*t/u
+}
+
+//Time for a shot fired straight up (y > 0) or down (y < 0) to reach height y, None if it tops out below it
+//The height is monotonic up to the top of the climb and all the way down, so bisection on t is enough
+fn vertical_flight_time(y: f64, u: f64, v: f64, g: f64) -> Option<f64> {
+    let a: f64 = if y > 0.0 { TAU/4.0 } else { -TAU/4.0 };
+    let mut lo: f64 = 0.0;
+    let mut hi: f64 = if y > 0.0 {
+        //Vertical velocity reaches zero at t = ln(1 + u*v/g)/u, v/g without drag
+        let top: f64 = if u == 0.0 { v/g } else { (u*v/g).ln_1p()/u };
+        if vertical_height(u, v, g, a, top) < y {
+            return None;
+        }
+        top
+    } else {
+        let mut hi: f64 = 1.0;
+        while vertical_height(u, v, g, a, hi) > y {
+            hi *= 2.0;
+            if !hi.is_finite() {
+                return None;
+            }
+        }
+        hi
+    };
+    for _ in 0..MAX_BISECTION_ITERATIONS {
+        let t: f64 = (lo + hi)/2.0;
+        if (vertical_height(u, v, g, a, t) < y) == (y > 0.0) {
+            lo = t;
+        } else {
+            hi = t;
+        }
+    }
+    Some((lo + hi)/2.0)
+}
+
+//Horizontal distance at which a shot fired at pitch a comes back down to the cannon's height
+//With drag the trajectory rises, then drops towards -infinity at the asymptote x = v*cos(a)/u, so bisect in between
+pub fn flat_range(u: f64, v: f64, g: f64, a: f64) -> f64 {
+    if a <= 0.0 {
+        return 0.0;
+    }
+    if u == 0.0 {
+        return v*v*(2.0*a).sin()/g;
+    }
+    let mut low: f64 = 0.0;
+    let mut high: f64 = v*a.cos()/u;
+    for _ in 0..MAX_BISECTION_ITERATIONS {
+        let mid: f64 = (low + high)/2.0;
+        if mid == low || mid == high { break }
+        if trajectory_height(mid, u, v, g, a) > 0.0 { low = mid } else { high = mid }
+    }
+    low
+}
+
+//Farthest distance a shot can land at the cannon's height, where the direct and indirect pitches merge
+//Past it even the critical point, the highest the trajectory gets at that distance, falls below the cannon
+//Without drag it's v²/g at 45°, with drag no shot gets past the asymptote v/u
+pub fn max_range(u: f64, v: f64, g: f64) -> f64 {
+    if check_input(0.0, 0.0, u, v, g).is_err() {
+        return f64::NAN;
+    }
+    if u == 0.0 {
+        return v*v/g;
+    }
+    let mut low: f64 = 0.0;
+    let mut high: f64 = v/u;
+    for _ in 0..MAX_BISECTION_ITERATIONS {
+        let mid: f64 = (low + high)/2.0;
+        if mid == low || mid == high { break }
+        let reachable: bool = find_critical_point(mid, u, v, g).is_ok_and(|a| trajectory_height(mid, u, v, g, a) >= 0.0);
+        if reachable { low = mid } else { high = mid }
+    }
+    low
+}
+
+//Near max range the height at the target barely changes with pitch, so small errors in the inputs move the solution a lot
+//Flags solutions where one degree of pitch shifts the height at the target by less than ~0.4% of the distance
+const SENSITIVE_SLOPE: f64 = 0.25;
+
+pub fn is_sensitive(x: f64, u: f64, v: f64, g: f64, a: f64) -> bool {
+    let h: f64 = 1e-6;
+    let slope: f64 = (trajectory_height(x, u, v, g, a + h) - trajectory_height(x, u, v, g, a - h))/(2.0*h);
+    slope.abs() < SENSITIVE_SLOPE*x
+}
+
+//Time of flight for a solved pitch angle
+//The horizontal motion is x(t) = v*cos(a)*(1 - e^(-u*t))/u, so 1 - p = e^(-u*t) with the same p as angle_check
+pub fn flight_time(x: f64, u: f64, v: f64, a: f64) -> f64 {
+    if u == 0.0 {
+        return x/(v*a.cos());
+    }
+    let p: f64 = (x*u)/(v*a.cos());
+    -(1.0-p).ln()/u
+}
+
+//Horizontal and vertical velocity at time t
+//Drag decays both components by e^(-u*t), gravity pulls the vertical one towards the terminal velocity -g/u
+fn velocity_at(u: f64, v: f64, g: f64, a: f64, t: f64) -> (f64, f64) {
+    if u == 0.0 {
+        return (v*a.cos(), v*a.sin() - g*t);
+    }
+    let decay: f64 = (-u*t).exp();
+    (v*a.cos()*decay, (v*a.sin() + g/u)*decay - g/u)
+}
+
+//Angle of the velocity at time t, in the same convention as the pitch (negative when descending)
+pub fn impact_angle(u: f64, v: f64, g: f64, a: f64, t: f64) -> f64 {
+    let (vx, vy) = velocity_at(u, v, g, a, t);
+    vy.atan2(vx)
+}
+
+//Speed left at time t, what AP penetration depends on
+pub fn impact_speed(u: f64, v: f64, g: f64, a: f64, t: f64) -> f64 {
+    let (vx, vy) = velocity_at(u, v, g, a, t);
+    vx.hypot(vy)
+}
+
+//Fuze timers are set in game ticks, 20 per second
+//Rounded to the nearest tick, the flight time and fuze lines both go through here so they never disagree
+pub const TICKS_PER_SECOND: f64 = 20.0;
+
+pub fn seconds_to_ticks(t: f64) -> f64 {
+    (t*TICKS_PER_SECOND).round()
+}
+
+//Lead for a target moving at a constant velocity (blocks/s): aim where the target will be after the flight time,
+//which changes the flight time, so repeat until it settles
+const MAX_LEAD_ITERATIONS: usize = 20;
+const LEAD_TOLERANCE: f64 = 1e-4;
+
+//target is relative to the cannon and t the flight time of the static solution
+//Returns the yaw and pitch to the lead point, None if the lead point is out of range or the flight time doesn't settle
+pub fn lead_solution(target: [f64; 3], velocity: [f64; 3], u: f64, v: f64, g: f64, indirect: bool, t: f64) -> Option<(f64, f64)> {
+    let mut t: f64 = t;
+    for _ in 0..MAX_LEAD_ITERATIONS {
+        let x: f64 = target[0] + velocity[0]*t;
+        let y: f64 = target[1] + velocity[1]*t;
+        let z: f64 = target[2] + velocity[2]*t;
+        let d: f64 = (x*x + z*z).sqrt();
+
+        let critical_point = find_critical_point(d, u, v, g).ok()?;
+        let angles = find_angles(d, y, u, v, g, critical_point).ok()?;
+        let pitch: f64 = if indirect { angles.1 } else { angles.0 };
+        if pitch.is_nan() {
+            return None;
+        }
+
+        let next_t: f64 = flight_time(d, u, v, pitch);
+        if (next_t - t).abs() < LEAD_TOLERANCE {
+            return Some((calc_yaw(x, z), pitch));
+        }
+        t = next_t;
+    }
+    None
+}
+
+//Elevation range of a standard cannon mount in degrees, autocannons and custom mounts can be wider
+pub const DEFAULT_MIN_ELEVATION: f64 = -30.0;
+pub const DEFAULT_MAX_ELEVATION: f64 = 60.0;
+
+//Whether the mount can be elevated to a pitch (radians) within limits given in degrees
+pub fn elevation_reachable(pitch: f64, min_elevation: f64, max_elevation: f64) -> bool {
+    let degrees = pitch.to_degrees();
+    min_elevation <= degrees && degrees <= max_elevation
+}
+
+//Nearest whole number of mount steps to a pitch (radians) and the aim error left by rounding to it, in degrees
+pub fn elevation_steps(pitch: f64, step: f64) -> (i64, f64) {
+    let degrees: f64 = pitch.to_degrees();
+    let steps: f64 = (degrees / step).round();
+    (steps as i64, steps * step - degrees)
+}
+
+/*
+          -X (90°)
+             ^
+             |
+-Z (180°) <--O--> +Z (0°)
+             |
+             v
+          +X (270°)
+*/
+pub fn calc_yaw(x: f64, z: f64) -> f64 {
+    let mut yaw: f64 = -x.atan2(z);
+    if yaw < 0.0 { yaw += TAU }
+    //A tiny negative yaw rounds up to exactly TAU when wrapped, which is the same direction as 0
+    if yaw >= TAU { yaw = 0.0 }
+    yaw
+}
+
+//World X/Z offset of a point given ahead of and to the right of a facing yaw (radians, calc_yaw's convention)
+//Facing south (0) forward is +Z and right is -X, the same rotation turns any other facing
+pub fn facing_offset(yaw: f64, forward: f64, right: f64) -> (f64, f64) {
+    let (sin, cos) = yaw.sin_cos();
+    (-forward*sin - right*cos, forward*cos - right*sin)
+}
+
+//Turn from one yaw to another the short way round, in (-π, π] radians
+//Positive turns right (south towards west, like calc_yaw counts), negative turns left
+pub fn shortest_yaw_delta(from: f64, to: f64) -> f64 {
+    let delta: f64 = (to - from).rem_euclid(TAU);
+    if delta > TAU/2.0 { delta - TAU } else { delta }
+}
+
+//Minecraft's own facing readout, which the cannon mount's yaw and pitch follow:
+//yaw in (-180°, 180°] from south like calc_yaw, pitch negative when aiming up. Both in degrees
+pub fn game_yaw(yaw: f64) -> f64 {
+    let degrees: f64 = yaw.to_degrees();
+    if degrees > 180.0 { degrees - 360.0 } else { degrees }
+}
+
+pub fn game_pitch(pitch: f64) -> f64 {
+    -pitch.to_degrees()
+}
+
+//Nearest 8-point compass direction of a yaw from calc_yaw, following F3 where south (+Z) is 0° and west (-X) is 90°
+pub fn compass_direction(yaw: f64) -> &'static str {
+    const DIRECTIONS: [&str; 8] = ["S", "SW", "W", "NW", "N", "NE", "E", "SE"];
+    DIRECTIONS[(yaw.to_degrees() / 45.0).round().rem_euclid(8.0) as usize]
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum AmmoType {
+    Shot,
+    APShot,
+    APShell,
+    HEShell,
+    MortarStone,
+    SmokeShell,
+    Custom
+}
+
+impl AmmoType {
+    pub const ALL: [AmmoType; 7] = [
+        AmmoType::Shot, AmmoType::APShot, AmmoType::APShell, AmmoType::HEShell, AmmoType::MortarStone, AmmoType::SmokeShell, AmmoType::Custom
+    ];
+
+    //Key that stays the same whatever the ammo is called or translated to
+    pub fn id(self) -> &'static str {
+        match self {
+            AmmoType::Shot        => "shot",
+            AmmoType::APShot      => "ap_shot",
+            AmmoType::APShell     => "ap_shell",
+            AmmoType::HEShell     => "he_shell",
+            AmmoType::MortarStone => "mortar_stone",
+            AmmoType::SmokeShell  => "smoke_shell",
+            AmmoType::Custom      => "custom",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<AmmoType> {
+        AmmoType::ALL.into_iter().find(|kind| kind.id() == id)
+    }
+}
+
+//drag is the per-second velocity decay used by angle_check, denser/more streamlined rounds lose less speed
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Ammo {
+    pub kind: AmmoType,
+    pub name: String,
+    pub drag: f64,
+    pub gravity: f64,
+    pub velocity_per_charge: f64,
+    pub max_charges: u32,
+    #[serde(default = "default_despawn_ticks")]
+    pub despawn_ticks: u32
+}
+
+//Powder charges one barrel block can burn, charges past what the whole barrel holds add no velocity
+//A starting value like the ammo's, not read from the mod
+pub const CHARGES_PER_BARREL_BLOCK: u32 = 1;
+
+pub fn barrel_capacity(barrel_blocks: u32) -> u32 {
+    barrel_blocks.saturating_mul(CHARGES_PER_BARREL_BLOCK)
+}
+
+//Projectiles are removed after a fixed lifetime, generous enough that only extreme lobs run into it
+pub const DEFAULT_DESPAWN_TICKS: u32 = 1200;
+
+fn default_despawn_ticks() -> u32 {
+    DEFAULT_DESPAWN_TICKS
+}
+
+//Starting values for the built-in ammo, not read from the mod's munition data
+//Every type gets the same drag and velocity rather than made-up differences, calibrate fits the real ones from test shots
+//Only the mortar stone's lower gravity comes from the original calculator
+pub const DEFAULT_DRAG: f64 = 0.01;
+pub const DEFAULT_GRAVITY: f64 = 10.0;
+pub const DEFAULT_VELOCITY_PER_CHARGE: f64 = 40.0;
+pub const DEFAULT_MAX_CHARGES: u32 = 8;
+
+impl Ammo {
+    fn preset(kind: AmmoType, name: &str, gravity: f64) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            drag: DEFAULT_DRAG,
+            gravity,
+            velocity_per_charge: DEFAULT_VELOCITY_PER_CHARGE,
+            max_charges: DEFAULT_MAX_CHARGES,
+            despawn_ticks: DEFAULT_DESPAWN_TICKS
+        }
+    }
+    pub fn shot() -> Self {
+        Self::preset(AmmoType::Shot, "Shot", DEFAULT_GRAVITY)
+    }
+    pub fn ap_shot() -> Self {
+        Self::preset(AmmoType::APShot, "AP Shot", DEFAULT_GRAVITY)
+    }
+    pub fn ap_shell() -> Self {
+        Self::preset(AmmoType::APShell, "AP Shell", DEFAULT_GRAVITY)
+    }
+    pub fn he_shell() -> Self {
+        Self::preset(AmmoType::HEShell, "HE Shell", DEFAULT_GRAVITY)
+    }
+    pub fn mortar_stone() -> Self {
+        Self::preset(AmmoType::MortarStone, "Mortar Stone", DEFAULT_GRAVITY/2.0)
+    }
+    pub fn smoke_shell() -> Self {
+        Self::preset(AmmoType::SmokeShell, "Smoke Shell", DEFAULT_GRAVITY)
+    }
+
+    //Whether the drag and velocity are still the shared starting values, so the results are only as good as those
+    pub fn is_uncalibrated(&self) -> bool {
+        self.drag == DEFAULT_DRAG && self.velocity_per_charge == DEFAULT_VELOCITY_PER_CHARGE
+    }
+
+    //User defined profile for worlds where modpacks or configs change the projectile physics, fired like a Shot
+    pub fn custom(name: &str, drag: f64, gravity: f64) -> Self {
+        Self {
+            kind: AmmoType::Custom,
+            name: name.to_string(),
+            drag,
+            gravity,
+            velocity_per_charge: DEFAULT_VELOCITY_PER_CHARGE,
+            max_charges: DEFAULT_MAX_CHARGES,
+            despawn_ticks: DEFAULT_DESPAWN_TICKS
+        }
+    }
+
+    //Charges that actually add velocity, capped by what the projectile can take and, when it's known, what the barrel can burn
+    pub fn effective_charges(&self, charges: u32, barrel_blocks: Option<u32>) -> u32 {
+        charges.min(self.max_charges).min(barrel_blocks.map_or(u32::MAX, barrel_capacity))
+    }
+
+    //Velocity grows linearly with the powder charges, charges past the cap add nothing
+    pub fn muzzle_velocity(&self, charges: u32, barrel_blocks: Option<u32>) -> f64 {
+        self.velocity_per_charge * self.effective_charges(charges, barrel_blocks) as f64
+    }
+
+    //Fewest charges that put a target d blocks away and y blocks up in range, None if even the full load falls short
+    //g is the gravity actually in effect, the ammo's own scaled by the world multiplier
+    pub fn min_charges(&self, d: f64, y: f64, g: f64, barrel_blocks: Option<u32>) -> Option<u32> {
+        (1..=self.effective_charges(self.max_charges, barrel_blocks)).find(|&charges| {
+            let v: f64 = self.muzzle_velocity(charges, barrel_blocks);
+            firing_solution(d, y, self.drag, v, g).is_ok()
+        })
+    }
+
+    //Charges and arc whose impact angle comes closest to a wanted one, like a steep plunge onto a roof
+    //wanted is in the pitch convention, negative for a shell coming down, None when no charge count reaches the target
+    pub fn charges_for_impact_angle(&self, d: f64, y: f64, g: f64, barrel_blocks: Option<u32>, wanted: f64) -> Option<ImpactChoice> {
+        (1..=self.effective_charges(self.max_charges, barrel_blocks))
+            .filter_map(|charges| Some((charges, firing_solution(d, y, self.drag, self.muzzle_velocity(charges, barrel_blocks), g).ok()?)))
+            .flat_map(|(charges, solution)| [false, true].map(|indirect| ImpactChoice {
+                charges,
+                indirect,
+                pitch: solution.pitch.arc(indirect),
+                impact_angle: solution.impact_angle.arc(indirect),
+                error: solution.impact_angle.arc(indirect) - wanted
+            }))
+            .filter(|choice| choice.error.is_finite())
+            .min_by(|a, b| a.error.abs().total_cmp(&b.error.abs()))
+    }
+
+    //Whether a projectile in flight for t seconds is removed before it lands
+    pub fn despawns(&self, t: f64) -> bool {
+        seconds_to_ticks(t) > self.despawn_ticks as f64
+    }
+
+    //Shells that burst on a fuze timer instead of on impact
+    pub fn is_timed(&self) -> bool {
+        matches!(self.kind, AmmoType::HEShell | AmmoType::SmokeShell)
+    }
+
+    //Built-in values of a kind, custom ammo starts out with the shot's drag and gravity
+    pub fn select(kind: AmmoType) -> Ammo {
+        match kind {
+            AmmoType::Shot        => Ammo::shot(),
+            AmmoType::APShot      => Ammo::ap_shot(),
+            AmmoType::APShell     => Ammo::ap_shell(),
+            AmmoType::HEShell     => Ammo::he_shell(),
+            AmmoType::MortarStone => Ammo::mortar_stone(),
+            AmmoType::SmokeShell  => Ammo::smoke_shell(),
+            AmmoType::Custom      => Ammo::custom("Custom", Ammo::shot().drag, Ammo::shot().gravity),
+        }
+    }
+
+    //Built-ins are keyed by kind alone, their shown name comes from the translations
+    //Loaded profiles and named custom ammo can share a kind (e.g. "HE Shell v1.2"), so their name tells them apart
+    pub fn id(&self) -> String {
+        if self.name == Ammo::select(self.kind).name {
+            self.kind.id().to_string()
+        } else {
+            format!("{}:{}", self.kind.id(), self.name)
+        }
+    }
+
+    pub fn builtin() -> Vec<Ammo> {
+        vec![Ammo::shot(), Ammo::ap_shot(), Ammo::ap_shell(), Ammo::he_shell(), Ammo::mortar_stone(), Ammo::smoke_shell()]
+    }
+
+    //Ammo offered by the selector: the built-ins, each replaced by a loaded profile of the same name, then the other profiles
+    pub fn registry(profiles: &[Ammo]) -> Vec<Ammo> {
+        let mut registry: Vec<Ammo> = Ammo::builtin().into_iter()
+            .map(|ammo| profiles.iter().find(|profile| profile.name == ammo.name).cloned().unwrap_or(ammo))
+            .collect();
+        for profile in profiles {
+            if !registry.iter().any(|ammo| ammo.name == profile.name) {
+                registry.push(profile.clone());
+            }
+        }
+        registry
+    }
+    
+}
+
+impl PartialEq for Ammo {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Pair<T = f64> {
+    pub direct_shot: T,
+    pub indirect_shot: T
+}
+
+impl<T: Copy> Pair<T> {
+    pub fn arc(&self, indirect: bool) -> T {
+        if indirect { self.indirect_shot } else { self.direct_shot }
+    }
+}
+
+//Both firing solutions for a target d blocks away and y blocks up, angles in radians and times in seconds
+#[derive(Clone, Copy)]
+pub struct FiringSolution {
+    pub pitch: Pair,
+    pub time: Pair,
+    pub impact_angle: Pair,
+    pub impact_speed: Pair,
+    pub info: SolveInfo
+}
+
+//The projectile spawns at the muzzle, l blocks from the mount's pivot along the aim, which moves with the pitch
+//So each arc is solved again from the muzzle its own pitch puts the shell at until the pitch settles
+const MAX_BARREL_ITERATIONS: u32 = 20;
+const BARREL_TOLERANCE: f64 = 1e-6;
+
+//Returns the corrected solution and how far each arc's pitch moved from the one solved at the pivot
+pub fn muzzle_solution(d: f64, y: f64, u: f64, v: f64, g: f64, l: f64, solver: Solver) -> Result<(FiringSolution, Pair), SolveError> {
+    let pivot = solver.firing_solution(d, y, u, v, g)?;
+    if l == 0.0 || !l.is_finite() {
+        return Ok((pivot, Pair { direct_shot: 0.0, indirect_shot: 0.0 }));
+    }
+
+    let mut corrected = pivot;
+    let mut error = SolveError::OutOfRange;
+    for indirect in [false, true] {
+        let mut pitch: f64 = pivot.pitch.arc(indirect);
+        let mut settled: Option<FiringSolution> = None;
+        if pitch.is_finite() {
+            for _ in 0..MAX_BARREL_ITERATIONS {
+                let Ok(solution) = solver.firing_solution(d - l*pitch.cos(), y - l*pitch.sin(), u, v, g) else { break };
+                let next: f64 = solution.pitch.arc(indirect);
+                if !next.is_finite() {
+                    break
+                }
+                let done: bool = (next - pitch).abs() < BARREL_TOLERANCE;
+                pitch = next;
+                if done {
+                    settled = Some(solution);
+                    break
+                }
+            }
+            if settled.is_none() {
+                error = SolveError::NoConvergence;
+            }
+        }
+
+        let values = settled.map(|solution| [solution.pitch.arc(indirect), solution.time.arc(indirect), solution.impact_angle.arc(indirect), solution.impact_speed.arc(indirect)])
+            .unwrap_or([f64::NAN; 4]);
+        let fields = [&mut corrected.pitch, &mut corrected.time, &mut corrected.impact_angle, &mut corrected.impact_speed];
+        for (field, value) in fields.into_iter().zip(values) {
+            if indirect { field.indirect_shot = value } else { field.direct_shot = value }
+        }
+    }
+
+    if corrected.pitch.direct_shot.is_nan() && corrected.pitch.indirect_shot.is_nan() {
+        return Err(error);
+    }
+    let shift = Pair {
+        direct_shot: corrected.pitch.direct_shot - pivot.pitch.direct_shot,
+        indirect_shot: corrected.pitch.indirect_shot - pivot.pitch.indirect_shot
+    };
+    Ok((corrected, shift))
+}
+
+//Target straight above or below, the arc formulas all divide by the horizontal distance
+//Fired straight at it there's a single shot, reported as the direct one
+pub fn vertical_solution(y: f64, u: f64, v: f64, g: f64) -> Result<FiringSolution, SolveError> {
+    if y.abs() < VERTICAL_TOLERANCE {
+        return Err(SolveError::InvalidInput(InputError::SamePosition));
+    }
+    let a: f64 = if y > 0.0 { TAU/4.0 } else { -TAU/4.0 };
+    let t: f64 = vertical_flight_time(y, u, v, g).ok_or(SolveError::OutOfRange)?;
+    Ok(FiringSolution {
+        pitch: Pair { direct_shot: a, indirect_shot: f64::NAN },
+        time: Pair { direct_shot: t, indirect_shot: f64::NAN },
+        impact_angle: Pair { direct_shot: impact_angle(u, v, g, a, t), indirect_shot: f64::NAN },
+        impact_speed: Pair { direct_shot: impact_speed(u, v, g, a, t), indirect_shot: f64::NAN },
+        info: SolveInfo { iterations: MAX_BISECTION_ITERATIONS, newton_iterations: 0, method: "Vertical bisection" }
+    })
+}
+
+//The whole solve pipeline on plain numbers, shared by the tabs and the command line
+pub fn firing_solution(d: f64, y: f64, u: f64, v: f64, g: f64) -> Result<FiringSolution, SolveError> {
+    check_input(d, y, u, v, g)?;
+    if d < VERTICAL_TOLERANCE {
+        return vertical_solution(y, u, v, g);
+    }
+    if beyond_drag_limit(d, u, v, 0.0) {
+        return Err(SolveError::OutOfRange);
+    }
+    let critical_point = find_critical_point(d, u, v, g)?;
+    let (direct, indirect, info) = find_angles(d, y, u, v, g, critical_point)?;
+
+    let time = Pair { direct_shot: flight_time(d, u, v, direct), indirect_shot: flight_time(d, u, v, indirect) };
+    Ok(FiringSolution {
+        pitch: Pair { direct_shot: direct, indirect_shot: indirect },
+        impact_angle: Pair {
+            direct_shot: impact_angle(u, v, g, direct, time.direct_shot),
+            indirect_shot: impact_angle(u, v, g, indirect, time.indirect_shot)
+        },
+        impact_speed: Pair {
+            direct_shot: impact_speed(u, v, g, direct, time.direct_shot),
+            indirect_shot: impact_speed(u, v, g, indirect, time.indirect_shot)
+        },
+        time,
+        info
+    })
+}
+
+//Create Big Cannons moves projectiles in whole ticks: the shell moves by its velocity, then drag scales the velocity and gravity pulls it down
+//angle_check smooths that into continuous motion, the simulation steps through the ticks for when the difference matters
+//Drag over one tick is e^(-u/20), the decay angle_check applies over the same time, so only the stepping differs
+#[derive(Clone, Copy, Default, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Solver {
+    #[default]
+    Analytic,
+    Simulation
+}
+
+impl Solver {
+    pub fn firing_solution(self, d: f64, y: f64, u: f64, v: f64, g: f64) -> Result<FiringSolution, SolveError> {
+        match self {
+            Solver::Analytic => firing_solution(d, y, u, v, g),
+            Solver::Simulation => simulated_solution(d, y, u, v, g)
+        }
+    }
+}
+
+//Height, time and velocity of a simulated shot where it crosses the target's horizontal distance
+struct Crossing {
+    height: f64,
+    time: f64,
+    velocity: (f64, f64)
+}
+
+//Only a backstop, shots that can't reach the target end once they drop below it
+const MAX_SIMULATION_TICKS: u32 = 100_000;
+
+//Step a shot at pitch a tick by tick until it passes x, interpolating within the tick that crosses it
+//None when it falls below y first, once descending it can only keep falling
+fn simulate_shot(x: f64, y: f64, u: f64, v: f64, g: f64, a: f64) -> Option<Crossing> {
+    let dt: f64 = 1.0/TICKS_PER_SECOND;
+    let decay: f64 = (-u*dt).exp();
+    let (mut px, mut py): (f64, f64) = (0.0, 0.0);
+    let (mut vx, mut vy): (f64, f64) = (v*a.cos(), v*a.sin());
+    for tick in 0..MAX_SIMULATION_TICKS {
+        if px + vx*dt >= x {
+            let s: f64 = (x - px)/(vx*dt);
+            return Some(Crossing { height: py + s*vy*dt, time: (tick as f64 + s)*dt, velocity: (vx, vy) });
+        }
+        px += vx*dt;
+        py += vy*dt;
+        if py < y && vy < 0.0 {
+            return None;
+        }
+        vx *= decay;
+        vy = vy*decay - g*dt;
+    }
+    None
+}
+
+//How far above the target a simulated shot passes, -infinity when it never gets there
+fn simulated_miss(x: f64, y: f64, u: f64, v: f64, g: f64, a: f64) -> f64 {
+    simulate_shot(x, y, u, v, g, a).map_or(f64::NEG_INFINITY, |crossing| crossing.height - y)
+}
+
+//Pitch between lo and hi where the miss changes sign, lo and hi on either side of it
+//Returns the end that still reaches the target, so simulating it again always gives a crossing, and the steps it took
+fn simulated_root(x: f64, y: f64, u: f64, v: f64, g: f64, mut lo: f64, mut hi: f64) -> (f64, u32) {
+    let below: bool = simulated_miss(x, y, u, v, g, lo) < 0.0;
+    let mut steps: u32 = 0;
+    for _ in 0..MAX_BISECTION_ITERATIONS {
+        let mid: f64 = (lo + hi)/2.0;
+        if mid == lo || mid == hi { break }
+        steps += 1;
+        if (simulated_miss(x, y, u, v, g, mid) < 0.0) == below { lo = mid } else { hi = mid }
+    }
+    (if below { hi } else { lo }, steps)
+}
+
+//The miss rises to a single peak between straight down and straight up, like angle_check around its critical point
+//A coarse scan finds the peak to within a degree, a golden section search narrows it, and each arc is bisected on its side
+const SIMULATION_SCAN_STEPS: u32 = 180;
+const GOLDEN_SECTION_ITERATIONS: u32 = 100;
+
+pub fn simulated_solution(d: f64, y: f64, u: f64, v: f64, g: f64) -> Result<FiringSolution, SolveError> {
+    check_input(d, y, u, v, g)?;
+    //Straight up or down there's nothing to interpolate over, the analytic climb is used as is
+    if d < VERTICAL_TOLERANCE {
+        return vertical_solution(y, u, v, g);
+    }
+    let miss = |a: f64| simulated_miss(d, y, u, v, g, a);
+
+    let step: f64 = (TAU/2.0)/SIMULATION_SCAN_STEPS as f64;
+    let pitch_at = |i: u32| -TAU/4.0 + step*i as f64;
+    let misses: Vec<f64> = (0..=SIMULATION_SCAN_STEPS).map(|i| miss(pitch_at(i))).collect();
+    let peak: u32 = (0..=SIMULATION_SCAN_STEPS).max_by(|&i, &j| misses[i as usize].total_cmp(&misses[j as usize])).unwrap_or(0);
+
+    let ratio: f64 = (5.0_f64.sqrt() - 1.0)/2.0;
+    let (mut lo, mut hi): (f64, f64) = (pitch_at(peak.saturating_sub(1)), pitch_at((peak + 1).min(SIMULATION_SCAN_STEPS)));
+    for _ in 0..GOLDEN_SECTION_ITERATIONS {
+        let c: f64 = hi - ratio*(hi - lo);
+        let e: f64 = lo + ratio*(hi - lo);
+        if miss(c) < miss(e) { lo = c } else { hi = e }
+    }
+    let top: f64 = (lo + hi)/2.0;
+    if miss(top) < 0.0 {
+        return Err(SolveError::OutOfRange);
+    }
+
+    //Both ends miss completely, the shell never gets any horizontal distance fired straight up or down
+    let (direct, direct_steps) = simulated_root(d, y, u, v, g, -TAU/4.0, top);
+    let (indirect, indirect_steps) = simulated_root(d, y, u, v, g, top, TAU/4.0);
+    let mut solution = FiringSolution {
+        pitch: Pair { direct_shot: direct, indirect_shot: indirect },
+        time: Pair { direct_shot: f64::NAN, indirect_shot: f64::NAN },
+        impact_angle: Pair { direct_shot: f64::NAN, indirect_shot: f64::NAN },
+        impact_speed: Pair { direct_shot: f64::NAN, indirect_shot: f64::NAN },
+        info: SolveInfo {
+            iterations: GOLDEN_SECTION_ITERATIONS + direct_steps + indirect_steps,
+            newton_iterations: 0,
+            method: "Tick simulation"
+        }
+    };
+    for indirect in [false, true] {
+        let pitch: f64 = if indirect { solution.pitch.indirect_shot } else { solution.pitch.direct_shot };
+        let Some(crossing) = simulate_shot(d, y, u, v, g, pitch) else { continue };
+        let (vx, vy) = crossing.velocity;
+        let values = [crossing.time, vy.atan2(vx), vx.hypot(vy)];
+        let fields = [&mut solution.time, &mut solution.impact_angle, &mut solution.impact_speed];
+        for (field, value) in fields.into_iter().zip(values) {
+            if indirect { field.indirect_shot = value } else { field.direct_shot = value }
+        }
+    }
+    Ok(solution)
+}
+
+//Aim for one target given in world coordinates, angles in radians and times in seconds
+//Arcs the cannon can't fire are NaN like in FiringSolution
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub yaw: f64,
+    pub pitch: Pair,
+    pub time: Pair,
+    pub impact_angle: Pair,
+    pub impact_speed: Pair
+}
+
+//Solve a target from the cannon's and the target's block coordinates, the entry point for other tools
+pub fn solve(cannon: [f64; 3], target: [f64; 3], ammo: &Ammo, charges: u32) -> Result<Solution, SolveError> {
+    let x: f64 = target[0] - cannon[0];
+    let y: f64 = target[1] - cannon[1];
+    let z: f64 = target[2] - cannon[2];
+    let d: f64 = (x*x + z*z).sqrt();
+    let solution = firing_solution(d, y, ammo.drag, ammo.muzzle_velocity(charges, None), ammo.gravity)?;
+    Ok(Solution {
+        yaw: calc_yaw(x, z),
+        pitch: solution.pitch,
+        time: solution.time,
+        impact_angle: solution.impact_angle,
+        impact_speed: solution.impact_speed
+    })
+}
+
+//Closest match to a wanted impact angle, error is how far the achieved angle is from it, angles in radians
+#[derive(Clone, Copy, Debug)]
+pub struct ImpactChoice {
+    pub charges: u32,
+    pub indirect: bool,
+    pub pitch: f64,
+    pub impact_angle: f64,
+    pub error: f64
+}
+
+//One cannon's part in a salvo, angles in radians
+#[derive(Clone, Copy, Debug)]
+pub struct SalvoShot {
+    pub charges: u32,
+    pub yaw: f64,
+    pub pitch: f64,
+    pub time: f64
+}
+
+//Every way a cannon can hit the target, both arcs at each charge count, within the mount's elevation limits
+//target is relative to the cannon, g is the gravity in effect
+pub fn salvo_options(target: [f64; 3], ammo: &Ammo, g: f64, min_elevation: f64, max_elevation: f64) -> Vec<SalvoShot> {
+    let d: f64 = (target[0]*target[0] + target[2]*target[2]).sqrt();
+    let yaw: f64 = calc_yaw(target[0], target[2]);
+    let mut options: Vec<SalvoShot> = Vec::new();
+    for charges in 1..=ammo.max_charges {
+        let Ok(solution) = firing_solution(d, target[1], ammo.drag, ammo.muzzle_velocity(charges, None), g) else { continue };
+        for (pitch, time) in [
+            (solution.pitch.direct_shot, solution.time.direct_shot),
+            (solution.pitch.indirect_shot, solution.time.indirect_shot)
+        ] {
+            if pitch.is_finite() && elevation_reachable(pitch, min_elevation, max_elevation) {
+                options.push(SalvoShot { charges, yaw, pitch, time });
+            }
+        }
+    }
+    options
+}
+
+//Pick one option per cannon so the flight times end up as close together as possible
+//Each option's time is tried as the anchor with every cannon taking its option closest to it, the tightest spread wins
+//Cannons without options are left out, None when none have any
+pub fn plan_salvo(options: &[Vec<SalvoShot>]) -> Option<Vec<Option<SalvoShot>>> {
+    let mut best: Option<(f64, Vec<Option<SalvoShot>>)> = None;
+    for anchor in options.iter().flatten() {
+        let plan: Vec<Option<SalvoShot>> = options.iter()
+            .map(|cannon| cannon.iter().copied().min_by(|a, b| (a.time - anchor.time).abs().total_cmp(&(b.time - anchor.time).abs())))
+            .collect();
+        let times = plan.iter().flatten().map(|shot| shot.time);
+        let spread: f64 = times.clone().fold(f64::NEG_INFINITY, f64::max) - times.fold(f64::INFINITY, f64::min);
+        if best.as_ref().is_none_or(|(best_spread, _)| spread < *best_spread) {
+            best = Some((spread, plan));
+        }
+    }
+    best.map(|(_, plan)| plan)
+}
+
+//Common impact time of a planned salvo, every cannon fires its flight time before it
+//The longest flight fires first, at 0
+pub fn salvo_impact_time<'a>(shots: impl Iterator<Item = &'a SalvoShot>) -> f64 {
+    shots.map(|shot| shot.time).fold(f64::NAN, f64::max)
+}
+
+//A shot fired in-game and where it came down, relative to the cannon
+//charges are the ones that added velocity, after the ammo's and the barrel's caps
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Observation {
+    pub charges: u32,
+    pub pitch: f64,
+    pub distance: f64,
+    pub height: f64
+}
+
+//Drag and velocity per charge fitted to observed shots, residual is the root mean square height miss in blocks
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Calibration {
+    pub drag: f64,
+    pub velocity_per_charge: f64,
+    pub residual: f64
+}
+
+//How far the model puts each observed shot above or below where it landed, the y(x) that angle_check scales
+//None when a shot can't reach its landing point with these constants at all
+fn calibration_misses(observations: &[Observation], g: f64, u: f64, k: f64) -> Option<Vec<f64>> {
+    observations.iter().map(|shot| {
+        let v: f64 = k * shot.charges as f64;
+        if check_input(shot.distance, shot.height, u, v, g).is_err() || beyond_drag_limit(shot.distance, u, v, shot.pitch) {
+            return None;
+        }
+        let miss: f64 = trajectory_height(shot.distance, u, v, g, shot.pitch) - shot.height;
+        miss.is_finite().then_some(miss)
+    }).collect()
+}
+
+//Least squares fit of drag and velocity per charge with Levenberg-Marquardt, starting from the ammo's current values
+//Gravity is taken as known. Needs two shots for two unknowns, None when there are fewer or no constants fit them
+const MAX_CALIBRATION_ITERATIONS: u32 = 200;
+
+pub fn calibrate(observations: &[Observation], g: f64, drag: f64, velocity_per_charge: f64) -> Option<Calibration> {
+    if observations.len() < 2 || observations.iter().any(|shot| !shot.pitch.is_finite() || shot.pitch.abs() >= PITCH_LIMIT || shot.charges == 0) {
+        return None;
+    }
+    let cost = |misses: &[f64]| misses.iter().map(|miss| miss*miss).sum::<f64>();
+    //Without drag every shot reaches some distance, so that's the fallback start when the current drag falls short
+    let (mut u, mut k): (f64, f64) = (drag.max(0.0), velocity_per_charge);
+    let mut misses: Vec<f64> = match calibration_misses(observations, g, u, k) {
+        Some(misses) => misses,
+        None => {
+            u = 0.0;
+            calibration_misses(observations, g, u, k)?
+        }
+    };
+    let mut damping: f64 = 1e-3;
+
+    for _ in 0..MAX_CALIBRATION_ITERATIONS {
+        //Less drag and more velocity both move away from the drag limit, so those are the safe sides to difference on
+        let du: f64 = if u > 1e-7 { -f64::max(u*1e-6, 1e-7) } else { 1e-7 };
+        let dk: f64 = k*1e-6;
+        let by_u: Vec<f64> = calibration_misses(observations, g, u + du, k)?;
+        let by_k: Vec<f64> = calibration_misses(observations, g, u, k + dk)?;
+        let jacobian: Vec<[f64; 2]> = misses.iter().enumerate()
+            .map(|(i, miss)| [(by_u[i] - miss)/du, (by_k[i] - miss)/dk])
+            .collect();
+
+        let mut a: [[f64; 2]; 2] = [[0.0; 2]; 2];
+        let mut gradient: [f64; 2] = [0.0; 2];
+        for (row, miss) in jacobian.iter().zip(&misses) {
+            for i in 0..2 {
+                gradient[i] += row[i]*miss;
+                for j in 0..2 {
+                    a[i][j] += row[i]*row[j];
+                }
+            }
+        }
+
+        let current: f64 = cost(&misses);
+        let mut improved = false;
+        while damping < 1e12 {
+            let m = [[a[0][0]*(1.0 + damping), a[0][1]], [a[1][0], a[1][1]*(1.0 + damping)]];
+            let determinant: f64 = m[0][0]*m[1][1] - m[0][1]*m[1][0];
+            let step = [
+                -(gradient[0]*m[1][1] - gradient[1]*m[0][1])/determinant,
+                -(m[0][0]*gradient[1] - m[1][0]*gradient[0])/determinant
+            ];
+            let (next_u, next_k) = ((u + step[0]).max(0.0), k + step[1]);
+            if let Some(next) = calibration_misses(observations, g, next_u, next_k).filter(|next| next_k > 0.0 && cost(next) < current) {
+                improved = current - cost(&next) > 1e-15*current;
+                (u, k, misses) = (next_u, next_k, next);
+                damping = (damping/10.0).max(1e-12);
+                break;
+            }
+            damping *= 10.0;
+        }
+        if !improved {
+            break;
+        }
+    }
+    Some(Calibration { drag: u, velocity_per_charge: k, residual: (cost(&misses)/observations.len() as f64).sqrt() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //pre-calculated data set
+    //x, y, u, v, g, a, t
+    const TESTING_DATA: [[f64; 7]; 13] = [
+        [   23.541096135,    0.959446698, 0.01,  30.0, 10.0,  0.174532925, 0.8 ],
+        [  187.001956030,   63.079770828, 0.01, 200.0, 10.0,  0.349065850, 1.0 ],
+        [   64.467192584,   26.026190686, 0.01,  50.0, 10.0,  0.523598776, 1.5 ],
+        [ 1132.001739726,  905.308887445, 0.01, 500.0, 10.0,  0.698131701, 3.0 ],
+        [ 1709.752036132, 1993.049776655, 0.01, 900.0, 10.0,  0.872664626, 3.0 ],
+        [   54.698606123,   88.712887372, 0.01, 100.0, 10.0,  1.047197551, 1.1 ],
+        [  249.003450881,  -58.274490171, 0.01, 150.0, 10.0, -0.174532925, 1.7 ],
+        [   28.120418992,  -11.482914756, 0.01,  60.0, 10.0, -0.349065850, 0.5 ],
+        [   86.602540378,   30.000000000, 0.00,  50.0, 10.0,  0.523598776, 2.0 ],
+        //Mortar stones fall at half the gravity, from a single charge up to the long lobs of a full load
+        [   24.354711353,   13.474855629, 0.015, 25.0,  5.0,  0.610865238, 1.2 ],
+        [  841.268927764,  366.570415646, 0.015, 100.0, 5.0,  0.698131701, 12.0 ],
+        [  539.569935044,  629.496216467, 0.015, 100.0, 5.0,  1.308996939, 25.0 ],
+        [   97.018225659,  -27.007676326, 0.015, 50.0,  5.0, -0.174532925, 2.0 ],
+    ];
+
+    #[test]
+    fn angle_calculation() {
+        for i in TESTING_DATA {
+            let crit = find_critical_point(i[0], i[2], i[3], i[4]).unwrap();
+            let angles = find_angles(i[0], i[1], i[2], i[3], i[4], crit);
+
+            match angles {
+                Ok(angle) => {
+                    if ! ( (0.00001 > (angle.1 - i[5]).abs()) || (0.00001 > (angle.0 - i[5]).abs())) {
+                        panic!("Failiure on test conditions {} {} {} {} {} {} {}, got crit {} and angles {} {}", i[0], i[1], i[2], i[3], i[4], i[5], i[6], crit, angle.0, angle.1)
+                    }
+                }
+                _ => {panic!("Unexpected outcome, find_angles didn't return anything")} //May change
+            }
+        }
+    }
+
+    #[test]
+    fn low_gravity() {
+        //Every distance a mortar stone reaches at each charge, both arcs land on the target
+        let mortar = Ammo::mortar_stone();
+        for charges in 1..=mortar.max_charges {
+            let v = mortar.muzzle_velocity(charges, None);
+            let range = flat_range(mortar.drag, v, mortar.gravity, TAU/8.0);
+            for step in 1..20 {
+                let d = range*step as f64/20.0;
+                let solution = firing_solution(d, 0.0, mortar.drag, v, mortar.gravity).unwrap();
+                for pitch in [solution.pitch.direct_shot, solution.pitch.indirect_shot] {
+                    assert!(trajectory_height(d, mortar.drag, v, mortar.gravity, pitch).abs() < 1e-5, "{} charges at {} blocks", charges, d);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn flight_time_calculation() {
+        for i in TESTING_DATA {
+            let t = flight_time(i[0], i[2], i[3], i[5]);
+            assert!((t - i[6]).abs() < 0.00001, "Expected flight time {} for pitch {}, got {}", i[6], i[5], t);
+        }
+
+        //The lobbed shot to the same target stays in the air longer
+        let i = TESTING_DATA[2];
+        let crit = find_critical_point(i[0], i[2], i[3], i[4]).unwrap();
+        let (direct, indirect, _) = find_angles(i[0], i[1], i[2], i[3], i[4], crit).unwrap();
+        assert!(flight_time(i[0], i[2], i[3], indirect) > flight_time(i[0], i[2], i[3], direct));
+    }
+
+    #[test]
+    fn solve_api() {
+        let shot = Ammo::shot();
+        let solution = solve([10.0, 64.0, -20.0], [-90.0, 74.0, -20.0], &shot, 2).unwrap();
+        let expected = firing_solution(100.0, 10.0, shot.drag, shot.muzzle_velocity(2, None), shot.gravity).unwrap();
+        assert!((solution.yaw - TAU/4.0).abs() < 1e-9);
+        assert_eq!(solution.pitch.direct_shot, expected.pitch.direct_shot);
+        assert_eq!(solution.time.indirect_shot, expected.time.indirect_shot);
+        assert_eq!(solution.impact_angle.direct_shot, expected.impact_angle.direct_shot);
+
+        assert_eq!(solve([0.0; 3], [5000.0, 0.0, 0.0], &shot, 1).err(), Some(SolveError::OutOfRange));
+        assert_eq!(solve([0.0; 3], [1.0, 0.0, 1.0], &shot, 0).err(), Some(SolveError::InvalidInput(InputError::Charges)));
+    }
+
+    #[test]
+    fn charge_velocity() {
+        let shot = Ammo::shot();
+        assert_eq!(shot.muzzle_velocity(0, None), 0.0);
+        assert_eq!(shot.muzzle_velocity(1, None), shot.velocity_per_charge);
+        assert_eq!(shot.muzzle_velocity(3, None), 3.0*shot.velocity_per_charge);
+        assert_eq!(shot.muzzle_velocity(shot.max_charges + 20, None), shot.muzzle_velocity(shot.max_charges, None));
+    }
+
+    #[test]
+    fn barrel_capacity_cap() {
+        let shot = Ammo::shot();
+        let blocks: u32 = 4;
+        let capacity: u32 = barrel_capacity(blocks);
+        assert!(capacity < shot.max_charges);
+
+        //Under and at what the barrel holds every charge counts
+        assert_eq!(shot.effective_charges(capacity - 1, Some(blocks)), capacity - 1);
+        assert_eq!(shot.muzzle_velocity(capacity, Some(blocks)), capacity as f64*shot.velocity_per_charge);
+
+        //Over it the extra charges are clamped, like past the projectile's own limit
+        assert_eq!(shot.effective_charges(10, Some(blocks)), capacity);
+        assert_eq!(shot.muzzle_velocity(10, Some(blocks)), shot.muzzle_velocity(capacity, None));
+        assert_eq!(shot.effective_charges(10, None), shot.max_charges);
+        assert_eq!(shot.effective_charges(10, Some(100)), shot.max_charges);
+
+        //A short barrel can leave a target out of reach the full load would make
+        assert_eq!(shot.min_charges(300.0, 100.0, 10.0, Some(1)), None);
+        assert_eq!(shot.min_charges(300.0, 100.0, 10.0, Some(2)), Some(2));
+    }
+
+    #[test]
+    fn barrel_length() {
+        //No barrel leaves the solution as solved at the pivot
+        let (solution, shift) = muzzle_solution(200.0, 10.0, 0.01, 80.0, 10.0, 0.0, Solver::Analytic).unwrap();
+        let pivot = firing_solution(200.0, 10.0, 0.01, 80.0, 10.0).unwrap();
+        assert_eq!(solution.pitch.direct_shot, pivot.pitch.direct_shot);
+        assert_eq!(shift.direct_shot, 0.0);
+
+        //With one each arc's pitch is the solution from the muzzle that pitch puts the shell at
+        let l: f64 = 6.0;
+        let (solution, shift) = muzzle_solution(200.0, 10.0, 0.01, 80.0, 10.0, l, Solver::Analytic).unwrap();
+        for (pitch, indirect) in [(solution.pitch.direct_shot, false), (solution.pitch.indirect_shot, true)] {
+            let muzzle = firing_solution(200.0 - l*pitch.cos(), 10.0 - l*pitch.sin(), 0.01, 80.0, 10.0).unwrap();
+            let expected: f64 = if indirect { muzzle.pitch.indirect_shot } else { muzzle.pitch.direct_shot };
+            assert!((pitch - expected).abs() < 1e-5);
+        }
+        assert!(shift.direct_shot != 0.0 && shift.indirect_shot != 0.0);
+        assert!((solution.pitch.direct_shot - pivot.pitch.direct_shot - shift.direct_shot).abs() < 1e-12);
+    }
+
+    #[test]
+    fn vertical_targets() {
+        //Straight up without drag the flight time follows y = v*t - g*t²/2
+        let solution = firing_solution(0.0, 100.0, 0.0, 100.0, 10.0).unwrap();
+        assert_eq!(solution.pitch.direct_shot, TAU/4.0);
+        assert!(solution.pitch.indirect_shot.is_nan());
+        let t: f64 = (100.0 - (100.0f64.powi(2) - 2.0*10.0*100.0).sqrt())/10.0;
+        assert!((solution.time.direct_shot - t).abs() < 1e-9);
+
+        //Higher than the shot can climb, and straight down with drag
+        assert_eq!(firing_solution(0.0, 1000.0, 0.0, 100.0, 10.0).err(), Some(SolveError::OutOfRange));
+        let solution = firing_solution(0.0, -50.0, 0.01, 100.0, 10.0).unwrap();
+        assert_eq!(solution.pitch.direct_shot, -TAU/4.0);
+        assert!((vertical_height(0.01, 100.0, 10.0, -TAU/4.0, solution.time.direct_shot) + 50.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn newton_fast_path() {
+        //The analytic slope matches a central difference
+        for i in TESTING_DATA {
+            let h: f64 = 1e-7;
+            let numeric = (angle_check(i[0], i[1], i[2], i[3], i[5] + h, i[4]) - angle_check(i[0], i[1], i[2], i[3], i[5] - h, i[4]))/(2.0*h);
+            let slope = angle_check_slope(i[0], i[2], i[3], i[5], i[4]);
+            assert!((numeric - slope).abs() < 1e-5*slope.abs().max(1.0), "Expected slope {} at pitch {}, got {}", numeric, i[5], slope);
+        }
+
+        //From a 0.1° step past the root, like the bracket find_angles sets up, Newton's method settles every test case alone
+        //angle_check is flat without drag, that case never gets here
+        for i in TESTING_DATA.into_iter().filter(|i| i[2] != 0.0) {
+            let crit = find_critical_point(i[0], i[2], i[3], i[4]).unwrap();
+            let step: f64 = 0.1f64.to_radians() * (i[5] - crit).signum();
+            let (root, _) = newton_root(i[0], i[1], i[2], i[3], i[4], crit, i[5] + step).unwrap();
+            assert!((root - i[5]).abs() < 1e-6);
+        }
+
+        //Reported through SolveInfo, here the direct arc settles by Newton's method and the lob falls back to bisecting
+        let i = TESTING_DATA[1];
+        let crit = find_critical_point(i[0], i[2], i[3], i[4]).unwrap();
+        let (direct, _, info) = find_angles(i[0], i[1], i[2], i[3], i[4], crit).unwrap();
+        assert_eq!(info.method, "Bisection fallback");
+        assert!(info.newton_iterations > 0 && info.newton_iterations < info.iterations - info.newton_iterations);
+        //Bisecting the direct arc's bracket instead takes more steps for the same root
+        let (bisected, steps) = bisection_root(i[0], i[1], i[2], i[3], i[4], crit, -PITCH_LIMIT + PITCH_SEARCH_STEP).unwrap();
+        assert!((bisected - direct).abs() < 1e-6);
+        assert!(info.newton_iterations < steps);
+    }
+
+    #[test]
+    fn critical_point_guard() {
+        //No velocity leaves regula falsi with equal ends and half-typed input gives NaN everywhere, both used to loop forever
+        assert_eq!(find_critical_point(100.0, 0.01, 0.0, 10.0), Err(SolveError::NoConvergence));
+        assert_eq!(find_critical_point(f64::NAN, 0.01, 100.0, 10.0), Err(SolveError::NoConvergence));
+
+        //A target straight above or below still has a critical point, straight up
+        assert!((find_critical_point(0.0, 0.01, 100.0, 10.0).unwrap() - TAU/4.0).abs() < 1e-9);
+
+        //Far past the range of a single charge
+        let shot = Ammo::shot();
+        assert!(firing_solution(730.0, 0.0, shot.drag, shot.muzzle_velocity(1, None), shot.gravity).is_err());
+    }
+
+    #[test]
+    fn out_of_range_error() {
+        let crit = find_critical_point(5000.0, 0.01, 50.0, 10.0).unwrap();
+        assert_eq!(find_angles(5000.0, 0.0, 0.01, 50.0, 10.0, crit), Err(SolveError::OutOfRange));
+        assert_eq!(find_vacuum_angles(5000.0, 0.0, 50.0, 10.0), Err(SolveError::OutOfRange));
+    }
+
+    #[test]
+    fn apex_calculation() {
+        //Without drag the apex on flat ground is v² sin²(a)/2g
+        let a: f64 = 0.7;
+        let expected = (100.0*100.0*a.sin()*a.sin())/(2.0*10.0);
+        assert!((apex_height(2000.0, 0.0, 100.0, 10.0, a) - expected).abs() < 1e-9);
+
+        //Drag lowers it
+        assert!(apex_height(2000.0, 0.01, 100.0, 10.0, a) < expected);
+
+        //A shot that reaches the target while still climbing peaks at the target
+        let i = TESTING_DATA[5];
+        assert!((apex_height(i[0], i[2], i[3], i[4], i[5]) - i[1]).abs() < 0.0001);
+
+        //Shooting downwards never climbs
+        assert_eq!(apex_height(100.0, 0.01, 100.0, 10.0, -0.2), 0.0);
+    }
+
+    #[test]
+    fn test_fire_error() {
+        let (u, v, g) = (0.01, 80.0, 10.0);
+        let solution = firing_solution(300.0, 0.0, u, v, g).unwrap();
+        let (direct, indirect) = (solution.pitch.direct_shot, solution.pitch.indirect_shot);
+
+        //The exact pitch lands on the target
+        let (range, height) = aim_error(300.0, u, v, g, direct, direct);
+        assert!(range.abs() < 1e-6 && height.abs() < 1e-9);
+
+        //Raising a flat shot sends it long and high, raising a lob brings it in short and low
+        let step: f64 = 0.5f64.to_radians();
+        let (range, height) = aim_error(300.0, u, v, g, direct, direct + step);
+        assert!(range > 0.0 && height > 0.0);
+        let (range, height) = aim_error(300.0, u, v, g, indirect, indirect + step);
+        assert!(range < 0.0 && height < 0.0);
+        assert!((flat_range(u, v, g, indirect + step) - 300.0 - range).abs() < 1e-6);
+
+        //Without drag the range at the cannon's height is the closed form one
+        let a: f64 = 0.3;
+        assert!((pass_distance(0.0, 0.0, 100.0, 10.0, a, false).unwrap() - 100.0*100.0*(2.0*a).sin()/10.0).abs() < 1e-6);
+        assert_eq!(pass_distance(1000.0, u, v, g, a, true), None);
+    }
+
+    #[test]
+    fn elevation_limits() {
+        assert!(elevation_reachable(f64::to_radians(45.0), DEFAULT_MIN_ELEVATION, DEFAULT_MAX_ELEVATION));
+        assert!(elevation_reachable(f64::to_radians(-30.0), DEFAULT_MIN_ELEVATION, DEFAULT_MAX_ELEVATION));
+        assert!(!elevation_reachable(f64::to_radians(75.0), DEFAULT_MIN_ELEVATION, DEFAULT_MAX_ELEVATION));
+        assert!(!elevation_reachable(f64::to_radians(-45.0), DEFAULT_MIN_ELEVATION, DEFAULT_MAX_ELEVATION));
+        assert!(elevation_reachable(f64::to_radians(75.0), -90.0, 90.0));
+        assert!(!elevation_reachable(f64::NAN, -90.0, 90.0));
+    }
+
+    #[test]
+    fn yaw_quadrants() {
+        let cases = [
+            (0.0, 1.0, 0.0),
+            (-1.0, 0.0, 90.0),
+            (0.0, -1.0, 180.0),
+            (1.0, 0.0, 270.0),
+            (-1.0, 1.0, 45.0),
+            (-1.0, -1.0, 135.0),
+            (1.0, -1.0, 225.0),
+            (1.0, 1.0, 315.0),
+            (-100.0, 173.205080757, 30.0)
+        ];
+        for (x, z, degrees) in cases {
+            assert!((calc_yaw(x, z) - f64::to_radians(degrees)).abs() < 1e-9, "({}, {}) should be {}°", x, z, degrees);
+        }
+
+        for i in 0..360 {
+            let angle = f64::to_radians(i as f64 + 0.5);
+            let yaw = calc_yaw(angle.cos(), angle.sin());
+            assert!((0.0..TAU).contains(&yaw), "{} out of range", yaw);
+        }
+        assert!((0.0..TAU).contains(&calc_yaw(0.0, 1.0)));
+        assert!((0.0..TAU).contains(&calc_yaw(-0.0, 1.0)));
+        assert!((0.0..TAU).contains(&calc_yaw(1e-300, 1.0)));
+    }
+
+    #[test]
+    fn facing_offsets() {
+        //(yaw, forward, right) -> (x, z)
+        let cases = [
+            (0.0, 50.0, 0.0, 0.0, 50.0),
+            (0.0, 0.0, 10.0, -10.0, 0.0),
+            (90.0, 50.0, 10.0, -50.0, -10.0),
+            (180.0, 50.0, 10.0, 10.0, -50.0),
+            (270.0, 50.0, 10.0, 50.0, 10.0)
+        ];
+        for (yaw, forward, right, x, z) in cases {
+            let (dx, dz) = facing_offset(f64::to_radians(yaw), forward, right);
+            assert!((dx - x).abs() < 1e-9 && (dz - z).abs() < 1e-9, "{}°: ({}, {}) should be ({}, {})", yaw, dx, dz, x, z);
+        }
+
+        //Straight ahead points back along the facing, a step right turns the yaw clockwise seen from above
+        let yaw: f64 = f64::to_radians(30.0);
+        let (x, z) = facing_offset(yaw, 100.0, 0.0);
+        assert!((calc_yaw(x, z) - yaw).abs() < 1e-9);
+        let (x, z) = facing_offset(yaw, 100.0, 100.0);
+        assert!((calc_yaw(x, z) - f64::to_radians(75.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn yaw_deltas() {
+        let cases = [(10.0, 30.0, 20.0), (30.0, 10.0, -20.0), (350.0, 10.0, 20.0), (10.0, 350.0, -20.0), (0.0, 180.0, 180.0), (180.0, 0.0, 180.0), (90.0, 90.0, 0.0), (-170.0, 170.0, -20.0)];
+        for (from, to, delta) in cases {
+            let result: f64 = shortest_yaw_delta(f64::to_radians(from), f64::to_radians(to)).to_degrees();
+            assert!((result - delta).abs() < 1e-9, "{}° to {}° should be {}°, got {}°", from, to, delta, result);
+        }
+    }
+
+    #[test]
+    fn mount_steps() {
+        let (steps, residual) = elevation_steps(37.5_f64.to_radians(), 0.5);
+        assert_eq!(steps, 75);
+        assert!(residual.abs() < 1e-9);
+
+        //Rounds to the nearest step, the residual is how far the mount ends up from the exact pitch
+        let (steps, residual) = elevation_steps(37.3_f64.to_radians(), 0.5);
+        assert_eq!(steps, 75);
+        assert!((residual - 0.2).abs() < 1e-9);
+        let (steps, residual) = elevation_steps((-12.2_f64).to_radians(), 0.5);
+        assert_eq!(steps, -24);
+        assert!((residual - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn game_angles() {
+        assert_eq!(game_yaw(0.0), 0.0);
+        assert!((game_yaw(f64::to_radians(90.0)) - 90.0).abs() < 1e-9);
+        assert!((game_yaw(f64::to_radians(180.0)) - 180.0).abs() < 1e-9);
+        assert!((game_yaw(f64::to_radians(270.0)) + 90.0).abs() < 1e-9);
+        assert!((game_yaw(calc_yaw(1.0, -1.0)) + 135.0).abs() < 1e-9);
+
+        assert!((game_pitch(f64::to_radians(30.0)) + 30.0).abs() < 1e-9);
+        assert!((game_pitch(f64::to_radians(-10.0)) - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn compass_directions() {
+        let cases = [(0.0, "S"), (90.0, "W"), (180.0, "N"), (270.0, "E"), (45.0, "SW"), (315.0, "SE"), (22.4, "S"), (22.6, "SW"), (359.9, "S"), (337.6, "S")];
+        for (degrees, direction) in cases {
+            assert_eq!(compass_direction(f64::to_radians(degrees)), direction, "{}°", degrees);
+        }
+        assert_eq!(compass_direction(calc_yaw(1.0, 0.0)), "E");
+        assert_eq!(compass_direction(calc_yaw(0.0, -1.0)), "N");
+    }
+
+    #[test]
+    fn trajectory_sampling() {
+        for i in TESTING_DATA {
+            let points = trajectory_points(i[0], i[2], i[3], i[4], i[5]);
+            assert_eq!(points.len(), TRAJECTORY_SAMPLES + 1);
+            assert_eq!(points[0], [0.0, 0.0]);
+
+            let last = points[TRAJECTORY_SAMPLES];
+            assert!((last[0] - i[0]).abs() < 1e-9);
+            assert!((last[1] - i[1]).abs() < 0.0001, "Trajectory ends at {} instead of {}", last[1], i[1]);
+        }
+    }
+
+    #[test]
+    fn sensitivity_near_max_range() {
+        let (u, v, g) = (0.01, 100.0, 10.0);
+
+        //Flat ground max range, where the two solutions merge
+        let (mut lo, mut hi) = (1.0, 5000.0);
+        for _ in 0..60 {
+            let mid = (lo + hi)/2.0;
+            let crit = find_critical_point(mid, u, v, g).unwrap();
+            if find_angles(mid, 0.0, u, v, g, crit).is_ok() { lo = mid } else { hi = mid }
+        }
+
+        let near = 0.999*lo;
+        let crit = find_critical_point(near, u, v, g).unwrap();
+        let (direct, indirect, _) = find_angles(near, 0.0, u, v, g, crit).unwrap();
+        assert!(is_sensitive(near, u, v, g, direct));
+        assert!(is_sensitive(near, u, v, g, indirect));
+
+        let mid = 0.5*lo;
+        let crit = find_critical_point(mid, u, v, g).unwrap();
+        let (direct, indirect, _) = find_angles(mid, 0.0, u, v, g, crit).unwrap();
+        assert!(!is_sensitive(mid, u, v, g, direct));
+        assert!(!is_sensitive(mid, u, v, g, indirect));
+    }
+
+    #[test]
+    fn underpowered_shot() {
+        //Drag stops this shot within v/u = 20 blocks, a target 100 blocks out is past it at every pitch
+        let (x, u, v, g) = (100.0, 0.5, 10.0, 10.0);
+        assert_eq!(firing_solution(x, 0.0, u, v, g).map(|_| ()), Err(SolveError::OutOfRange));
+        assert_eq!(find_angles(x, 0.0, u, v, g, 0.3).map(|_| ()), Err(SolveError::OutOfRange));
+        assert!(beyond_drag_limit(x, u, v, 0.0) && !beyond_drag_limit(19.0, u, v, 0.0));
+        //Right at the limit ln(1 - p) would be -infinity
+        assert_eq!(firing_solution(20.0, -5.0, u, v, g).map(|_| ()), Err(SolveError::OutOfRange));
+        assert_eq!(Solver::Simulation.firing_solution(x, 0.0, u, v, g).map(|_| ()), Err(SolveError::OutOfRange));
+    }
+
+    #[test]
+    fn ammo_calibration() {
+        //Shots landed by ammo with a bit more drag and less velocity than the defaults assume
+        let (u, k, g) = (0.013, 37.0, 10.0);
+        let observations: Vec<Observation> = [(2, 20.0, 0.0), (4, 40.0, 5.0), (6, 10.0, -8.0), (8, 60.0, 0.0)].into_iter()
+            .map(|(charges, degrees, height)| {
+                let pitch: f64 = f64::to_radians(degrees);
+                let distance: f64 = landing_distance(height, u, k * charges as f64, g, pitch).unwrap();
+                Observation { charges, pitch, distance, height }
+            })
+            .collect();
+        let fit: Calibration = calibrate(&observations, g, 0.01, 40.0).unwrap();
+        assert!((fit.drag - u).abs() < 1e-6 && (fit.velocity_per_charge - k).abs() < 1e-4);
+        assert!(fit.residual < 1e-3);
+
+        //Measured to the nearest block the fit is close but no longer exact
+        let rounded: Vec<Observation> = observations.iter().map(|shot| Observation { distance: shot.distance.round(), ..*shot }).collect();
+        let fit: Calibration = calibrate(&rounded, g, 0.01, 40.0).unwrap();
+        assert!((fit.drag - u).abs() < 1e-3 && (fit.velocity_per_charge - k).abs() < 1.0);
+        assert!(fit.residual > 0.0);
+
+        //A start whose drag can't reach the shots falls back to no drag
+        assert!(calibrate(&observations, g, 1.0, 40.0).is_some_and(|fit| (fit.drag - u).abs() < 1e-6));
+        assert_eq!(calibrate(&observations[..1], g, 0.01, 40.0), None);
+    }
+
+    #[test]
+    fn fixed_pitch_landing() {
+        //Lands where the solver aimed it, on the way down
+        let (u, v, g) = (0.01, 80.0, 10.0);
+        let solution: FiringSolution = firing_solution(200.0, 10.0, u, v, g).unwrap();
+        assert!((landing_distance(10.0, u, v, g, solution.pitch.indirect_shot).unwrap() - 200.0).abs() < 1e-6);
+        let level: f64 = f64::to_radians(30.0);
+        assert!((landing_distance(0.0, u, v, g, level).unwrap() - flat_range(u, v, g, level)).abs() < 1e-6);
+        //Fired level it still comes down below the cannon
+        assert!(landing_distance(-10.0, u, v, g, 0.0).is_some_and(|x| (trajectory_height(x, u, v, g, 0.0) + 10.0).abs() < 1e-6));
+
+        assert_eq!(landing_distance(1000.0, u, v, g, level), None);
+        assert_eq!(landing_distance(0.0, u, v, g, TAU/4.0), None);
+        assert_eq!(landing_distance(0.0, u, 0.0, g, level), None);
+    }
+
+    #[test]
+    fn near_vertical_lob() {
+        //5 blocks out and 150 up, the lob is within a few degrees of vertical
+        let (x, y, u, v, g) = (5.0, 150.0, 0.01, 80.0, 10.0);
+        let critical_point: f64 = find_critical_point(x, u, v, g).unwrap();
+        let (direct, indirect, _) = find_angles(x, y, u, v, g, critical_point).unwrap();
+        for pitch in [direct, indirect] {
+            assert!(pitch.is_finite() && pitch.abs() < TAU/4.0);
+            assert!((trajectory_height(x, u, v, g, pitch) - y).abs() < 1e-6);
+        }
+        assert!(indirect > f64::to_radians(85.0));
+
+        //Straight up is past reach, no sign change anywhere inside ±90°
+        assert_eq!(find_angles(x, 400.0, u, v, g, critical_point).map(|_| ()), Err(SolveError::OutOfRange));
+        //A critical point at or past vertical can't bracket anything
+        assert_eq!(find_angles(x, y, u, v, g, TAU/4.0).map(|_| ()), Err(SolveError::OutOfRange));
+    }
+
+    #[test]
+    fn maximum_flat_range() {
+        assert!((max_range(0.0, 40.0, 10.0) - 160.0).abs() < 1e-9);
+        assert!(max_range(0.01, 0.0, 10.0).is_nan());
+
+        //Just inside the range both arcs exist, just past it neither does
+        for (u, v, g) in [(0.01, 40.0, 10.0), (0.01, 100.0, 10.0), (0.02, 120.0, 5.0)] {
+            let range: f64 = max_range(u, v, g);
+            assert!(range > flat_range(u, v, g, f64::to_radians(45.0)) - 1e-6 && range < v/u);
+            let inside: f64 = 0.999*range;
+            assert!(find_angles(inside, 0.0, u, v, g, find_critical_point(inside, u, v, g).unwrap()).is_ok());
+            let outside: f64 = 1.001*range;
+            assert!(find_angles(outside, 0.0, u, v, g, find_critical_point(outside, u, v, g).unwrap()).is_err());
+        }
+    }
+
+    #[test]
+    fn impact_angle_calculation() {
+        //Without drag a shot landing at launch height comes down at minus the launch angle
+        let a: f64 = 0.6;
+        let t: f64 = 2.0*100.0*a.sin()/10.0;
+        assert!((impact_angle(0.0, 100.0, 10.0, a, t) + a).abs() < 1e-9);
+
+        //Drag makes the descent on flat ground steeper than the launch
+        let crit = find_critical_point(500.0, 0.01, 100.0, 10.0).unwrap();
+        let (direct, _, _) = find_angles(500.0, 0.0, 0.01, 100.0, 10.0, crit).unwrap();
+        let t = flight_time(500.0, 0.01, 100.0, direct);
+        assert!(impact_angle(0.01, 100.0, 10.0, direct, t) < -direct);
+
+        //The lobbed shot comes down steeply, the direct one stays shallower
+        for i in TESTING_DATA {
+            let crit = find_critical_point(i[0], i[2], i[3], i[4]).unwrap();
+            let (direct, indirect, _) = find_angles(i[0], i[1], i[2], i[3], i[4], crit).unwrap();
+            let direct_impact = impact_angle(i[2], i[3], i[4], direct, flight_time(i[0], i[2], i[3], direct));
+            let indirect_impact = impact_angle(i[2], i[3], i[4], indirect, flight_time(i[0], i[2], i[3], indirect));
+            assert!(indirect_impact < 0.0);
+            assert!(indirect_impact < direct_impact);
+        }
+    }
+
+    #[test]
+    fn impact_speed_calculation() {
+        //Without drag the speed follows v^2 - 2*g*h, so a shot landing at launch height keeps its muzzle speed
+        let a: f64 = 0.6;
+        let t: f64 = 2.0*100.0*a.sin()/10.0;
+        assert!((impact_speed(0.0, 100.0, 10.0, a, t) - 100.0).abs() < 1e-9);
+        let t: f64 = 1.5;
+        let h: f64 = trajectory_height(100.0*a.cos()*t, 0.0, 100.0, 10.0, a);
+        assert!((impact_speed(0.0, 100.0, 10.0, a, t) - (100.0f64.powi(2) - 2.0*10.0*h).sqrt()).abs() < 1e-6);
+
+        //More drag leaves less speed at the target
+        let mut last: f64 = f64::INFINITY;
+        for u in [0.0, 0.005, 0.01, 0.02] {
+            let solution = firing_solution(300.0, 0.0, u, 100.0, 10.0).unwrap();
+            assert!(solution.impact_speed.direct_shot < last);
+            last = solution.impact_speed.direct_shot;
+        }
+    }
+
+    #[test]
+    fn fuze_timer() {
+        assert_eq!(seconds_to_ticks(2.0), 40.0);
+        assert_eq!(seconds_to_ticks(1.26), 25.0);
+        assert_eq!(seconds_to_ticks(0.0), 0.0);
+        //Rounded to the nearest tick, halves away from zero
+        assert_eq!(seconds_to_ticks(0.024), 0.0);
+        assert_eq!(seconds_to_ticks(0.025), 1.0);
+        assert_eq!(seconds_to_ticks(3.474), 69.0);
+        //A fractional tick past the despawn limit rounds back onto it, the shell is still there
+        let shot = Ammo::shot();
+        assert!(!shot.despawns(60.024));
+        assert!(shot.despawns(60.025));
+
+        assert!(Ammo::he_shell().is_timed());
+        assert!(Ammo::smoke_shell().is_timed());
+        assert!(!Ammo::shot().is_timed());
+        assert!(!Ammo::ap_shell().is_timed());
+        assert!(!Ammo::mortar_stone().is_timed());
+    }
+
+    #[test]
+    fn impact_angle_charges() {
+        let shot = Ammo::shot();
+        let (d, y, g) = (400.0, 0.0, shot.gravity);
+
+        //A shallow impact is the direct arc of a light load, a steep plunge needs more powder and the lob
+        let shallow = shot.charges_for_impact_angle(d, y, g, None, -20.0_f64.to_radians()).unwrap();
+        let steep = shot.charges_for_impact_angle(d, y, g, None, -85.0_f64.to_radians()).unwrap();
+        assert!(!shallow.indirect && shallow.error.abs() < 1.0_f64.to_radians());
+        assert!(steep.indirect && steep.charges > shallow.charges);
+        assert!(steep.error.abs() < 1.0_f64.to_radians());
+
+        //The reported angle is the one the chosen shot lands at
+        let v: f64 = shot.muzzle_velocity(steep.charges, None);
+        let solution = firing_solution(d, y, shot.drag, v, g).unwrap();
+        assert_eq!(steep.pitch, solution.pitch.indirect_shot);
+        assert_eq!(steep.impact_angle, solution.impact_angle.indirect_shot);
+        assert_eq!(steep.error, steep.impact_angle + 85.0_f64.to_radians());
+
+        assert!(shot.charges_for_impact_angle(10000.0, 0.0, g, None, -1.0).is_none());
+    }
+
+    #[test]
+    fn minimum_charges() {
+        let shot = Ammo::shot();
+        assert_eq!(shot.min_charges(100.0, 0.0, 10.0, None), Some(1));
+        assert_eq!(shot.min_charges(200.0, 0.0, 10.0, None), Some(2));
+        assert_eq!(shot.min_charges(300.0, 100.0, 10.0, None), Some(2));
+        assert_eq!(shot.min_charges(10000.0, 0.0, 10.0, None), None);
+        //Past v/u no pitch gets there, however much powder is loaded
+        assert_eq!(Ammo::mortar_stone().min_charges(40000.0, 0.0, 5.0, None), None);
+    }
+
+    #[test]
+    fn single_arc() {
+        //Right below a fast cannon the lob would need more than ~89.9° of pitch, only the direct arc is found
+        let shot = Ammo::shot();
+        let v = shot.muzzle_velocity(4, None);
+        let critical_point = find_critical_point(5.0, shot.drag, v, shot.gravity).unwrap();
+        let (direct, indirect, _) = find_angles(5.0, -100.0, shot.drag, v, shot.gravity, critical_point).unwrap();
+        assert!(direct.is_finite() && direct < 0.0);
+        assert!(indirect.is_nan());
+        assert!(trajectory_height(5.0, shot.drag, v, shot.gravity, direct) + 100.0 < 1e-6);
+
+        //Near max range both arcs still exist and close in on each other
+        let critical_point = find_critical_point(150.0, shot.drag, shot.muzzle_velocity(1, None), shot.gravity).unwrap();
+        let (direct, indirect, _) = find_angles(150.0, 0.0, shot.drag, shot.muzzle_velocity(1, None), shot.gravity, critical_point).unwrap();
+        assert!(direct.is_finite() && indirect.is_finite() && direct < indirect);
+    }
+
+    #[test]
+    fn range_table() {
+        let shot = Ammo::shot();
+        let v = shot.muzzle_velocity(1, None);
+
+        //The pitch that solves a flat target also has that target as its range
+        let critical_point = find_critical_point(120.0, shot.drag, v, shot.gravity).unwrap();
+        let (direct, indirect, _) = find_angles(120.0, 0.0, shot.drag, v, shot.gravity, critical_point).unwrap();
+        assert!((flat_range(shot.drag, v, shot.gravity, direct) - 120.0).abs() < 1e-6);
+        assert!((flat_range(shot.drag, v, shot.gravity, indirect) - 120.0).abs() < 1e-6);
+
+        assert!((flat_range(0.0, 50.0, 10.0, TAU/8.0) - 250.0).abs() < 1e-9);
+        assert!(flat_range(shot.drag, v, shot.gravity, TAU/8.0) < flat_range(0.0, v, shot.gravity, TAU/8.0));
+        assert_eq!(flat_range(shot.drag, v, shot.gravity, -0.1), 0.0);
+
+        //Past the velocity cap the range stops growing
+        let capped = flat_range(shot.drag, shot.muzzle_velocity(shot.max_charges, None), shot.gravity, TAU/8.0);
+        assert_eq!(flat_range(shot.drag, shot.muzzle_velocity(shot.max_charges + 2, None), shot.gravity, TAU/8.0), capped);
+    }
+
+    #[test]
+    fn salvo_planning() {
+        let shot = |charges: u32, time: f64| SalvoShot { charges, yaw: 0.0, pitch: 0.5, time };
+
+        //The second cannon's 5.5s option matches the first's 6s one better than anything near 2s
+        let plan = plan_salvo(&[vec![shot(2, 6.0), shot(3, 2.0)], vec![shot(1, 5.5), shot(4, 9.0)], vec![]]).unwrap();
+        assert_eq!(plan.len(), 3);
+        assert_eq!(plan[0].unwrap().charges, 2);
+        assert_eq!(plan[1].unwrap().charges, 1);
+        assert!(plan[2].is_none());
+        assert_eq!(salvo_impact_time(plan.iter().flatten()), 6.0);
+        assert!(plan_salvo(&[vec![], vec![]]).is_none());
+    }
+
+    #[test]
+    fn ammo_profiles() {
+        assert_eq!(Ammo::registry(&[]).len(), 6);
+
+        let json = r#"[
+            {"kind": "HEShell", "name": "HE Shell v1.2", "drag": 0.02, "gravity": 9.0, "velocity_per_charge": 36.0, "max_charges": 6},
+            {"kind": "Shot", "name": "Shot", "drag": 0.005, "gravity": 10.0, "velocity_per_charge": 40.0, "max_charges": 8}
+        ]"#;
+        let profiles: Vec<Ammo> = serde_json::from_str(json).unwrap();
+        let registry = Ammo::registry(&profiles);
+        assert_eq!(registry.len(), 7);
+        assert_eq!(registry[0].drag, 0.005);
+        assert!(registry[3] == Ammo::he_shell());
+        assert!(registry[6].is_timed() && registry[6].max_charges == 6);
+        assert!(registry[6] != Ammo::he_shell());
+
+        //A saved registry loads back to the same set
+        let saved = serde_json::to_string(&registry).unwrap();
+        let loaded: Vec<Ammo> = serde_json::from_str(&saved).unwrap();
+        assert!(Ammo::registry(&loaded) == registry);
+    }
+
+    #[test]
+    fn ammo_identity() {
+        for kind in AmmoType::ALL {
+            assert_eq!(AmmoType::from_id(kind.id()), Some(kind));
+            assert_eq!(Ammo::select(kind).kind, kind);
+        }
+        assert_eq!(AmmoType::from_id("HE Shell"), None);
+
+        //Same name, different kinds
+        let mut renamed = Ammo::ap_shot();
+        renamed.name = "Shot".to_string();
+        assert!(renamed != Ammo::shot());
+
+        //The values don't matter, only the kind and, past the built-in name, the name
+        assert!(Ammo::custom("Slug", 0.02, 10.0) == Ammo::custom("Slug", 0.01, 5.0));
+        assert!(Ammo::custom("Slug", 0.02, 10.0) != Ammo::custom("Dart", 0.02, 10.0));
+        assert_eq!(Ammo::custom("Slug", 0.02, 10.0).id(), "custom:Slug");
+        assert_eq!(Ammo::custom("Custom", 0.02, 10.0).id(), "custom");
+        let mut profile = Ammo::he_shell();
+        profile.drag = 0.0;
+        assert_eq!(profile.id(), "he_shell");
+    }
+
+    #[test]
+    fn despawn_limit() {
+        let shot = Ammo::shot();
+        assert!(!shot.despawns(2.0));
+        assert!(!shot.despawns(60.0));
+        assert!(shot.despawns(60.1));
+
+        //A short-lived profile loses a long lob
+        let lob = flight_time(100.0, shot.drag, 200.0, f64::to_radians(89.0));
+        let custom = Ammo { despawn_ticks: 100, ..Ammo::shot() };
+        assert!(custom.despawns(lob));
+
+        //Older profile files without the field get the default
+        let old: Ammo = serde_json::from_str(r#"{"kind": "Shot", "name": "Old", "drag": 0.01, "gravity": 10.0, "velocity_per_charge": 40.0, "max_charges": 8}"#).unwrap();
+        assert_eq!(old.despawn_ticks, DEFAULT_DESPAWN_TICKS);
+    }
+
+    #[test]
+    fn merged_roots() {
+        //A target just under the top of the highest arc that reaches its distance, angle_check at the critical point is ~1e-14
+        let (x, u, v, g) = (100.0, 0.01, 40.0, 10.0);
+        let crit = find_critical_point(x, u, v, g).unwrap();
+        let y: f64 = trajectory_height(x, u, v, g, crit) - 1e-9;
+        let (direct, indirect, info) = find_angles(x, y, u, v, g, crit).unwrap();
+        assert_eq!(info.method, "Critical point (equal roots)");
+        assert_eq!(direct, crit);
+        assert_eq!(indirect, crit);
+        assert!((trajectory_height(x, u, v, g, direct) - y).abs() < 1e-6);
+    }
+
+    #[test]
+    fn solve_details() {
+        //The lob's root sits close to the singularity near 90°, where Newton's method hands over to the bisection
+        let i = TESTING_DATA[2];
+        let crit = find_critical_point(i[0], i[2], i[3], i[4]).unwrap();
+        let (_, _, info) = find_angles(i[0], i[1], i[2], i[3], i[4], crit).unwrap();
+        assert_eq!(info.method, "Bisection fallback");
+        assert!(info.iterations > 0 && info.iterations < 2*MAX_BISECTION_ITERATIONS);
+
+        assert_eq!(firing_solution(300.0, 20.0, 0.0, 120.0, 10.0).unwrap().info.method, "Closed form (no drag)");
+        assert_eq!(firing_solution(0.0, 20.0, 0.01, 120.0, 10.0).unwrap().info.method, "Vertical bisection");
+        assert_eq!(simulated_solution(300.0, 20.0, 0.01, 120.0, 10.0).unwrap().info.method, "Tick simulation");
+    }
+
+    #[test]
+    fn tick_simulation() {
+        let (d, y, u, v, g) = (300.0, 20.0, 0.01, 120.0, 10.0);
+        let simulated = simulated_solution(d, y, u, v, g).unwrap();
+        let analytic = firing_solution(d, y, u, v, g).unwrap();
+        for (sim, exact) in [(simulated.pitch.direct_shot, analytic.pitch.direct_shot), (simulated.pitch.indirect_shot, analytic.pitch.indirect_shot)] {
+            //Stepping the same physics in ticks lands close to the continuous solution, but not on it
+            assert!((sim - exact).abs() < 1f64.to_radians() && sim != exact);
+            let crossing = simulate_shot(d, y, u, v, g, sim).unwrap();
+            assert!((crossing.height - y).abs() < 1e-6);
+        }
+        assert!(simulated.pitch.direct_shot < simulated.pitch.indirect_shot);
+        assert!((simulated.time.direct_shot - analytic.time.direct_shot).abs() < 0.1);
+        assert!(simulated.impact_angle.direct_shot < 0.0 && simulated.impact_speed.direct_shot < v);
+
+        //Past the furthest the shell gets at any pitch
+        assert_eq!(simulated_solution(3000.0, 0.0, u, 40.0, g).err(), Some(SolveError::OutOfRange));
+        assert_eq!(Solver::default(), Solver::Analytic);
+        assert!(Solver::Simulation.firing_solution(d, y, u, v, g).unwrap().pitch.direct_shot == simulated.pitch.direct_shot);
+    }
+}
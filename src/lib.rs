@@ -0,0 +1,2 @@
+//Projectile physics and ammo of Create: Big Cannons, shared by the calculator and usable on its own
+pub mod ballistics;
@@ -1,27 +1,126 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
 use eframe::{egui, NativeOptions};
-use egui::{ComboBox, Grid, RichText};
+use egui::{Color32, ComboBox, Grid, Key, KeyboardShortcut, Margin, Modifiers, RichText};
 use egui_dock::{DockArea, DockState, NodeIndex, SurfaceIndex};
+use egui_plot::{HLine, Legend, Line, LineStyle, MarkerShape, Plot, Points};
+use serde::{Deserialize, Serialize};
 
 use core::f64;
 use std::f64::consts::TAU;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use regex::Regex;
 
+use create_big_cannons_ballistics_calculator::ballistics::{
+    aim_error, apex_height, barrel_capacity, calc_yaw, check_input, compass_direction, elevation_reachable, elevation_steps, calibrate, facing_offset, flat_range, flight_time, impact_angle, impact_speed, landing_distance, max_range, game_pitch, game_yaw, shortest_yaw_delta,
+    is_sensitive, lead_solution, muzzle_solution, plan_salvo, salvo_impact_time, salvo_options, seconds_to_ticks, solve, Observation,
+    trajectory_height, trajectory_points, Ammo, AmmoType, Calibration, ImpactChoice, InputError, Pair, SalvoShot, SolveError, SolveInfo, Solver, DEFAULT_MAX_ELEVATION,
+    DEFAULT_MIN_ELEVATION, VERTICAL_TOLERANCE
+};
+
 const NORMAL_TEXT: f32 = 15.0;
 const TITLE_TEXT: f32 = 20.0;
 
 fn main() -> eframe::Result<()> {
-    let options = NativeOptions::default();
+    //Any arguments switch to the headless mode for scripts, without them the GUI starts as usual
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        match run_cli(&args) {
+            Ok(json) => {
+                println!("{}", json);
+                std::process::exit(0);
+            }
+            Err(message) => {
+                eprintln!("{}", message);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    //The last window geometry is restored by MyApp::new, this size is only seen on the first run
+    let options = NativeOptions {
+        viewport: egui::ViewportBuilder::default()
+            .with_inner_size(DEFAULT_WINDOW_SIZE)
+            .with_min_inner_size(MIN_WINDOW_SIZE),
+        persist_window: false,
+        ..NativeOptions::default()
+    };
     eframe::run_native(
         "Create Big Cannons - H's Ballistics Calculator",
         options,
-        Box::new(|_cc| Ok(Box::<MyApp>::default())),
+        Box::new(|cc| Ok(Box::new(MyApp::new(cc)))),
     )
 }
 
+const CLI_USAGE: &str = "Usage: --cannon x,y,z --target x,y,z [--ammo he_shell] [--charges 4]";
+
+//Output of the command line, angles in degrees (null when there's no such arc) and times in seconds
+#[derive(Serialize)]
+struct CliShot {
+    pitch: f64,
+    time: f64,
+    impact_angle: f64
+}
+
+#[derive(Serialize)]
+struct CliOutput {
+    yaw: f64,
+    direct: CliShot,
+    indirect: CliShot
+}
+
+//Solve one target from the command line and return the solution as JSON
+fn run_cli(args: &[String]) -> Result<String, String> {
+    let mut cannon: Option<[String; 3]> = None;
+    let mut target: Option<[String; 3]> = None;
+    let mut ammo: Ammo = Ammo::shot();
+    let mut charges: u32 = 1;
+
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or(format!("Missing value for {}\n{}", flag, CLI_USAGE))?;
+        match flag.as_str() {
+            "--cannon" => cannon = Some(parse_coordinates(value).ok_or(format!("Invalid cannon coordinates: {}", value))?),
+            "--target" => target = Some(parse_coordinates(value).ok_or(format!("Invalid target coordinates: {}", value))?),
+            //Either the stable id ("he_shell") or the English name of a built-in ("HE Shell")
+            "--ammo" => {
+                let kind: AmmoType = AmmoType::from_id(value)
+                    .or_else(|| Ammo::builtin().into_iter().find(|ammo| ammo.name == *value).map(|ammo| ammo.kind))
+                    .filter(|kind| *kind != AmmoType::Custom)
+                    .ok_or(format!("Unknown ammo type: {}", value))?;
+                ammo = Ammo::select(kind);
+            }
+            "--charges" => charges = value.parse().map_err(|_| format!("Invalid charge count: {}", value))?,
+            _ => return Err(format!("Unknown argument: {}\n{}", flag, CLI_USAGE)),
+        }
+    }
+    let cannon = cannon.ok_or(format!("Missing --cannon\n{}", CLI_USAGE))?.map(|c| optional_f64(&c, 0.0));
+    let target = target.ok_or(format!("Missing --target\n{}", CLI_USAGE))?.map(|t| optional_f64(&t, 0.0));
+    let solution = solve(cannon, target, &ammo, charges).map_err(|error| error.to_string())?;
+
+    let output = CliOutput {
+        yaw: solution.yaw.to_degrees(),
+        direct: CliShot {
+            pitch: solution.pitch.direct_shot.to_degrees(),
+            time: solution.time.direct_shot,
+            impact_angle: solution.impact_angle.direct_shot.to_degrees()
+        },
+        indirect: CliShot {
+            pitch: solution.pitch.indirect_shot.to_degrees(),
+            time: solution.time.indirect_shot,
+            impact_angle: solution.impact_angle.indirect_shot.to_degrees()
+        }
+    };
+    serde_json::to_string_pretty(&output).map_err(|error| error.to_string())
+}
+
+//Input filters run on every keystroke, so their regexes are compiled once on first use
+static SIGNED_FLOAT_RE: OnceLock<Regex> = OnceLock::new();
+static POSITIVE_INTEGER_RE: OnceLock<Regex> = OnceLock::new();
+
 pub fn verify_signed_float_input(s: &mut String) {
-    let re = Regex::new(r"^-?[0-9]*\.?[0-9]*").unwrap();
+    let re = SIGNED_FLOAT_RE.get_or_init(|| Regex::new(r"^-?[0-9]*\.?[0-9]*").unwrap());
     let cap = re.captures(s);
     if cap.is_none() {
         *s = "".to_string();
@@ -30,8 +129,14 @@ pub fn verify_signed_float_input(s: &mut String) {
     }
 }
 
+//What's left of a number partway through typing it: a sign or a decimal point with no digits yet
+//The filter lets these through so "-5" can be typed, but they don't parse
+pub fn is_partial_number(s: &str) -> bool {
+    matches!(s.trim(), "-" | "." | "-.")
+}
+
 pub fn verify_positive_integer_input(s: &mut String) {
-    let re = Regex::new(r"^[1-9]+[0-9]*").unwrap();
+    let re = POSITIVE_INTEGER_RE.get_or_init(|| Regex::new(r"^[1-9]+[0-9]*").unwrap());
     let cap = re.captures(s);
     if cap.is_none() {
         *s = "".to_string();
@@ -40,182 +145,426 @@ pub fn verify_positive_integer_input(s: &mut String) {
     }
 }
 
-//function whose roots are the pitch angles for targetting
-fn angle_check(x: f64, y: f64, u: f64, v: f64, a: f64, g: f64) -> f64 {
-    let p: f64 = (x*u)/(v*a.cos());
-    (u*u*x*(a.tan()))/g + p - (y*u*u)/g + (1.0-p).ln()
+//Three numbers as copied from the F3 screen, e.g. "XYZ: 123.45 / 64.00 / -678.90" or "Block: 123 64 -678",
+//separated by slashes, commas or spaces. The first such triple wins, so the "[x y z]" chunk after a Block line is ignored
+static COORDINATES_RE: OnceLock<Regex> = OnceLock::new();
+
+pub fn parse_coordinates(text: &str) -> Option<[String; 3]> {
+    let re = COORDINATES_RE.get_or_init(|| Regex::new(
+        r"(-?[0-9]+(?:\.[0-9]+)?)\s*[/,\s]\s*(-?[0-9]+(?:\.[0-9]+)?)\s*[/,\s]\s*(-?[0-9]+(?:\.[0-9]+)?)"
+    ).unwrap());
+    let cap = re.captures(text)?;
+    Some([1, 2, 3].map(|i| {
+        let mut value = cap[i].to_string();
+        verify_signed_float_input(&mut value);
+        value
+    }))
+}
+
+//X/Y/Z fields named for the problem list, e.g. "Cannon Y"
+fn axis_labels(name: Text, fields: [&String; 3], language: Language) -> [(String, &String); 3] {
+    let [x, y, z] = fields;
+    let name: &str = name.get(language);
+    [(format!("{} X", name), x), (format!("{} Y", name), y), (format!("{} Z", name), z)]
 }
 
-//Find critical point of angle_check through the regula falsi method to get the initial guess for root-finding and selecting direct and indirect shot pitch angles
-//Should be able to optimize it better, or use an external math crate if it becomes a problem
-fn find_critical_point(x: f64, u: f64, v: f64, g: f64) -> f64{
-    let mut a: f64 = (g*x).atan2(v*v);
-    let mut b: f64 = (g*x).atan2(-v*v);
-    let mut c: f64;
+//Coordinates on the clipboard, None when it holds none
+fn clipboard_coordinates() -> Option<[String; 3]> {
+    let text = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text());
+    text.ok().as_deref().and_then(parse_coordinates)
+}
 
-    loop {
-        let fa = g*x*a.sin() + u*v*x - v*v*a.cos();
-        let fb = g*x*b.sin() + u*v*x - v*v*b.cos();
+//Whether pasting into the fields would replace something already typed there
+fn would_overwrite(fields: &[&mut String; 3]) -> bool {
+    fields.iter().any(|field| !field.trim().is_empty())
+}
 
-        c = b - (fb * (b - a)) / (fb - fa);
-        
-        let fc = g*x*c.sin() + u*v*x - v*v*c.cos();
-        if fc.abs() < 0.00001 {
-            break
-        } else if fc.signum() == fa.signum() {
-            a = c;
-        } else {
-            b = c;
-        }
+//Which X/Y/Z fields a paste fills
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum PasteTarget {
+    Cannon,
+    Target,
+}
+
+//Single line text field running an input filter on every edit
+//A field left holding only a sign or a point gets a faint outline, but not while it's still being typed into
+fn input_field(ui: &mut egui::Ui, value: &mut String, filter: fn(&mut String), language: Language) -> egui::Response {
+    let mut response = ui.text_edit_singleline(value);
+    if response.changed() {
+        filter(value);
+    }
+    if !response.has_focus() && is_partial_number(value) {
+        ui.painter().rect_stroke(response.rect.expand(1.0), 2.0, egui::Stroke::new(1.0, ui.visuals().warn_fg_color.gamma_multiply(0.6)));
+        response = response.on_hover_text(Text::EnterNumberTip.get(language));
     }
+    response
+}
 
-    c
+//Input field that Tab and Shift+Tab reach through the given focus chain
+fn chained_field(ui: &mut egui::Ui, value: &mut String, filter: fn(&mut String), language: Language, chain: &mut Vec<egui::Id>) -> egui::Response {
+    let response = input_field(ui, value, filter, language);
+    chain.push(response.id);
+    response
 }
 
-//Use the secand method to find the roots of angle_check (Newton's method fails)
-//Currently itering until the precision of f64 causes a NaN return, so it could be optimized if that somehow becomes an issue
-//Considering moving to the regula falsi method to ensure convergence
-fn find_angles(x: f64, y: f64, u: f64, v: f64, g: f64, critical_point: f64) -> Result<(f64, f64), String>{
-    let mut angles: [f64; 2] = [0.0, 0.0];
-    
-    let cpa = angle_check(x, y, u, v, critical_point, g);
-    if cpa < 0.0 {
-        return Err("Out of range".to_string());
-    } else if cpa < 1e-12 {
-        return Ok((cpa, cpa));
+//Draggable alternative to a text field for fine adjustments, the number is written back to the string the tab reads
+//Empty or half-typed text starts from 0, or from min when that's higher
+fn stepper_field(ui: &mut egui::Ui, value: &mut String, decimals: usize, min: f64, chain: &mut Vec<egui::Id>) -> egui::Response {
+    let mut number: f64 = value.trim().parse::<f64>().unwrap_or(0.0).max(min);
+    let response = ui.add(egui::DragValue::new(&mut number).range(min..=f64::INFINITY).max_decimals(decimals));
+    if response.changed() {
+        *value = number.to_string();
     }
-    
-    for i in 0..2 {
-        let mut a: f64 = critical_point;
+    chain.push(response.id);
+    response
+}
 
-        let mut b = - 0.011111111 / TAU; // -4°
-        if i == 1 { b += TAU/4.0; }
-        else { b -= TAU/4.0; }
-        
-        loop {
-            let fb = angle_check(x, y, u, v, b, g);
-            if fb < 0.0 { break }
-            else {
-                if i == 0 { b += 0.0017453292519943296; } // 0.1°
-                else { b-= 0.0017453292519943296; }
-            }
-        }
-
-        let mut c: f64;
-        loop {
-            let fa = angle_check(x, y, u, v, a, g);
-            let fb = angle_check(x, y, u, v, b, g);
-
-            c = b - (fb * (b - a)) / (fb - fa);
-            
-            let fc = angle_check(x, y, u, v, c, g);
-            if fc.abs() < 1e-12 {
-                break
-            } else if fc.signum() == fa.signum() {
-                a = c;
-            } else if fc.signum() == fb.signum() {
-                b = c;
-            } else {
-                panic!("Impossible Error (angle_check returned NAN)");
-            }
-        }
-        angles[i] = c;  
-    }
-
-    Ok((angles[0], angles[1]))
-}
-
-/*
-          -X (90°)
-             ^
-             |
--Z (180°) <--O--> +Z (0°)
-             |
-             v
-          +X (180°)
-*/
-pub fn calc_yaw(x: f64, z: f64) -> f64 {
-    let mut yaw: f64 = -x.atan2(z);
-    if yaw < 0.0 { yaw += TAU }
-    yaw
-}
-struct Ammo {
-    name: String,
-    drag: f64,
-    gravity: f64
+//Coordinate input, a stepper when they're turned on and a filtered text field otherwise
+fn coordinate_field(ui: &mut egui::Ui, value: &mut String, stepper: bool, language: Language, chain: &mut Vec<egui::Id>) -> egui::Response {
+    if stepper {
+        stepper_field(ui, value, 3, f64::NEG_INFINITY, chain)
+    } else {
+        chained_field(ui, value, verify_signed_float_input, language, chain)
+    }
 }
 
-impl Ammo {
-    fn shot() -> Self {
-        Self {
-            name: "Shot".to_string(),
-            drag: 0.01,
-            gravity: 10.0
-        }
+//Tab number Ctrl+Tab (or Ctrl+Shift+Tab when backwards) moves to, wrapping around at either end
+//None when there's no other tab to go to or the current one isn't in the order
+fn cycle_tab(order: &[usize], current: usize, backwards: bool) -> Option<usize> {
+    let i = order.iter().position(|n| *n == current)?;
+    if order.len() < 2 {
+        return None;
     }
-    fn ap_shot() -> Self {
-        Self {
-            name: "AP Shot".to_string(),
-            drag: 0.01,
-            gravity: 10.0
-        }
+    let next: usize = if backwards { (i + order.len() - 1) % order.len() } else { (i + 1) % order.len() };
+    Some(order[next])
+}
+
+//Where Tab (or Shift+Tab when backwards) goes from the focused widget, None to leave it to egui past either end
+fn next_in_chain(chain: &[egui::Id], focused: egui::Id, backwards: bool) -> Option<egui::Id> {
+    let i = chain.iter().position(|id| *id == focused)?;
+    if backwards {
+        i.checked_sub(1).map(|i| chain[i])
+    } else {
+        chain.get(i + 1).copied()
     }
-    fn ap_shell() -> Self {
-        Self {
-            name: "AP Shell".to_string(),
-            drag: 0.01,
-            gravity: 10.0
-        }
+}
+
+//egui moves focus in layout order, which interleaves side-by-side grids, so the fields in a chain are walked in their own order
+//focused is the widget that had focus before this frame's widgets were laid out, requesting focus afterwards overrides egui's pick
+fn follow_focus_chain(ui: &egui::Ui, chain: &[egui::Id], focused: Option<egui::Id>) {
+    let (tab, backwards) = ui.input(|i| (i.key_pressed(egui::Key::Tab), i.modifiers.shift));
+    if let Some(next) = focused.filter(|_| tab).and_then(|focused| next_in_chain(chain, focused, backwards)) {
+        ui.memory_mut(|m| m.request_focus(next));
     }
-    fn he_shell() -> Self {
-        Self {
-            name: "HE Shell".to_string(),
-            drag: 0.01,
-            gravity: 10.0
-        }
+}
+
+//Pressing Enter in a single line text field makes it lose focus
+fn enter_pressed(response: &egui::Response) -> bool {
+    response.lost_focus() && response.ctx.input(|i| i.key_pressed(egui::Key::Enter))
+}
+
+//Parse an optional numeric field: empty means the given default, anything unparseable (e.g. a lone "-") is NaN
+//so a half-typed value is never silently treated as 0
+pub fn optional_f64(s: &str, default: f64) -> f64 {
+    let s = s.trim();
+    if s.is_empty() {
+        return default;
     }
-    fn mortar_stone() -> Self {
-        Self {
-            name: "Mortar Stone".to_string(),
-            drag: 0.01,
-            gravity: 5.0
+    s.parse::<f64>().unwrap_or(f64::NAN)
+}
+
+//Heuristic for a common typo: the same number typed or pasted into two of the X/Y/Z fields
+//Two zeros are legitimate (e.g. cannon at the origin) so only equal non-zero values are flagged
+pub fn has_duplicate_coordinate(x: &str, y: &str, z: &str) -> bool {
+    let values: Vec<f64> = [x, y, z].iter()
+        .filter_map(|s| s.parse::<f64>().ok())
+        .filter(|v| *v != 0.0)
+        .collect();
+
+    for i in 0..values.len() {
+        for j in (i + 1)..values.len() {
+            if values[i] == values[j] { return true }
         }
     }
-    fn smoke_shell() -> Self {
-        Self {
-            name: "Smoke Shell".to_string(),
-            drag: 0.01,
-            gravity: 10.0
-        }
+    false
+}
+
+//Plot colors for the two arcs, apart enough to tell them apart in both themes
+const DIRECT_COLOR: Color32 = Color32::from_rgb(80, 160, 255);
+const INDIRECT_COLOR: Color32 = Color32::from_rgb(255, 150, 50);
+const CLEAR_COLOR: Color32 = Color32::from_rgb(80, 180, 80);
+
+//How far an arc passes above a point at [distance, height], None when the point isn't between the cannon and the target
+fn clearance(input: SolveInput, pitch: f64, point: [f64; 2]) -> Option<f64> {
+    if point[0] <= input.muzzle(pitch)[0] || point[0] >= input.distance || !pitch.is_finite() {
+        return None;
     }
+    Some(input.height_at(point[0], pitch) - point[1])
+}
+
+//Whether an arc passes above an obstacle at [distance, height], None when the obstacle isn't between the cannon and the target
+fn clears_obstacle(input: SolveInput, pitch: f64, obstacle: [f64; 2]) -> Option<bool> {
+    clearance(input, pitch, obstacle).map(|clearance| clearance > 0.0)
+}
+
+//Lowest pass over the friendly positions as (distance, clearance), None when none of them is under the arc
+fn friendly_pass(input: SolveInput, pitch: f64, friendlies: &[[f64; 2]]) -> Option<(f64, f64)> {
+    friendlies.iter()
+        .filter_map(|point| Some((point[0], clearance(input, pitch, *point)?)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
 
-    fn select(ammo_type: &str) -> Ammo {
-        match ammo_type {
-            "Shot"          => { Ammo::shot() }
-            "AP Shot"       => { Ammo::ap_shot() }
-            "AP Shell"      => { Ammo::ap_shell() }
-            "HE Shell"      => { Ammo::he_shell() }
-            "Mortar Stone"  => { Ammo::mortar_stone() }
-            "Smoke Shell"   => { Ammo::smoke_shell() }
-            _ => {Ammo::shot()}
+//Arc that stays above every terrain sample, the flatter one when both do, None when neither clears
+//Returns whether it's the indirect arc, samples outside the cannon-target span are ignored
+fn terrain_arc(input: SolveInput, pitch: Pair, terrain: &[[f64; 2]]) -> Option<bool> {
+    [false, true].into_iter().find(|indirect| {
+        let pitch: f64 = pitch.arc(*indirect);
+        pitch.is_finite() && terrain.iter().all(|point| clears_obstacle(input, pitch, *point) != Some(false))
+    })
+}
+
+//Upper bound on the rows of a range table, so a mistyped charge count can't stall the UI
+const MAX_TABLE_ROWS: u32 = 32;
+
+//Compact multi-line summary of one firing solution, angles in radians are printed in degrees
+pub fn solution_text(name: &str, yaw: f64, pitch: f64, time: f64, impact_angle: f64, decimals: usize) -> String {
+    format!(
+        "{}\nYaw: {:.*}°\nPitch: {:.*}°\nFlight time: {:.*}s\nImpact angle: {:.*}°",
+        name, decimals, yaw.to_degrees(), decimals, pitch.to_degrees(), decimals, time, decimals, impact_angle.to_degrees()
+    )
+}
+
+//Size of the trajectory sketch in a Markdown report, in characters
+const ASCII_PLOT_SIZE: (usize, usize) = (60, 15);
+
+//Rough character plot of trajectories given as (distance, height) points, each arc drawn with its own mark
+//Scaled so every point fits, the first row is the highest
+fn ascii_trajectory(arcs: &[(Vec<[f64; 2]>, char)], width: usize, height: usize) -> String {
+    let points = arcs.iter().flat_map(|(points, _)| points.iter()).filter(|p| p[0].is_finite() && p[1].is_finite());
+    let (mut x_max, mut y_min, mut y_max): (f64, f64, f64) = (0.0, 0.0, 0.0);
+    for p in points {
+        x_max = x_max.max(p[0]);
+        y_min = y_min.min(p[1]);
+        y_max = y_max.max(p[1]);
+    }
+    let mut grid: Vec<Vec<char>> = vec![vec![' '; width]; height];
+    for (points, mark) in arcs {
+        for p in points.iter().filter(|p| p[0].is_finite() && p[1].is_finite()) {
+            let column: usize = if x_max > 0.0 { (p[0] / x_max * (width - 1) as f64).round() as usize } else { 0 };
+            let row: usize = if y_max > y_min { ((y_max - p[1]) / (y_max - y_min) * (height - 1) as f64).round() as usize } else { height - 1 };
+            grid[row.min(height - 1)][column.min(width - 1)] = *mark;
         }
     }
-    
+    grid.iter().map(|row| row.iter().collect::<String>().trim_end().to_string() + "\n").collect()
 }
 
-impl PartialEq for Ammo {
-    fn eq(&self, other: &Self) -> bool {
-        self.name == other.name
+const CSV_HEADER: &str = "cannon_x,cannon_y,cannon_z,target_x,target_y,target_z,ammo,charges,yaw,direct_pitch,indirect_pitch,direct_time,indirect_time,direct_impact_angle,indirect_impact_angle";
+
+//Quote a CSV field if it holds a separator or quote, custom ammo names are free text
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+//Angles are written in degrees, anything without a solution is left empty
+//Adding 0.0 turns a -0.0 (e.g. calc_yaw straight south) into 0.0 so it isn't written as "-0.0000"
+fn csv_number(n: f64, degrees: bool) -> String {
+    match (n.is_finite(), degrees) {
+        (false, _) => String::new(),
+        (true, true) => format!("{:.4}", n.to_degrees() + 0.0),
+        (true, false) => format!("{:.4}", n + 0.0)
+    }
+}
+
+//One row per tab with a header on top
+fn solutions_csv<'a>(tabs: impl Iterator<Item = &'a MyTab>) -> String {
+    let mut csv: String = CSV_HEADER.to_string();
+    csv.push('\n');
+    for tab in tabs {
+        csv.push_str(&tab.csv_row());
+        csv.push('\n');
+    }
+    csv
+}
+
+//Inputs of the last successful solve, kept so the trajectories can be redrawn every frame
+//distance and height are from the mount's pivot, the shell leaves from the muzzle barrel blocks along the aim
+#[derive(Clone, Copy)]
+struct SolveInput {
+    distance: f64,
+    height: f64,
+    drag: f64,
+    velocity: f64,
+    gravity: f64,
+    barrel: f64
+}
+
+impl SolveInput {
+    //Offset of the muzzle from the pivot at a pitch, as [distance, height]
+    fn muzzle(self, pitch: f64) -> [f64; 2] {
+        [self.barrel*pitch.cos(), self.barrel*pitch.sin()]
+    }
+
+    //The same target seen from the muzzle, which is where the pitches are solved from
+    fn at_muzzle(self, pitch: f64) -> SolveInput {
+        let [x, y] = self.muzzle(pitch);
+        SolveInput { distance: self.distance - x, height: self.height - y, barrel: 0.0, ..self }
+    }
+
+    //Height of the arc above the pivot at a distance from it, NaN before the muzzle
+    fn height_at(self, distance: f64, pitch: f64) -> f64 {
+        let [x, y] = self.muzzle(pitch);
+        if distance < x {
+            return f64::NAN;
+        }
+        y + trajectory_height(distance - x, self.drag, self.velocity, self.gravity, pitch)
+    }
+
+    //Plot points of one arc from the muzzle to the target, in the pivot's frame like the target and obstacles
+    fn points(self, pitch: f64) -> Vec<[f64; 2]> {
+        let [x, y] = self.muzzle(pitch);
+        let muzzle: SolveInput = self.at_muzzle(pitch);
+        trajectory_points(muzzle.distance, self.drag, self.velocity, self.gravity, pitch).into_iter()
+            .map(|point| [point[0] + x, point[1] + y])
+            .collect()
+    }
+
+    //The same launch without drag, sampled out to where it comes back down to the target's height
+    fn vacuum_points(self, pitch: f64) -> Vec<[f64; 2]> {
+        let [x, y] = self.muzzle(pitch);
+        let muzzle: SolveInput = self.at_muzzle(pitch);
+        let landing: f64 = landing_distance(muzzle.height, 0.0, self.velocity, self.gravity, pitch).unwrap_or(muzzle.distance);
+        trajectory_points(landing, 0.0, self.velocity, self.gravity, pitch).into_iter()
+            .map(|point| [point[0] + x, point[1] + y])
+            .collect()
     }
 }
 
-struct Pair {
-    pub direct_shot: f64,
-    pub indirect_shot: f64
+//Aim point corrected for the target's movement, holding the static solution when the lead didn't converge
+#[derive(Clone, Copy)]
+struct Lead {
+    yaw: f64,
+    pitch: f64,
+    converged: bool
+}
+
+//Where a fixed-pitch shot comes down, worked out every frame on the fixed pitch tab
+#[derive(Clone, Copy, Debug)]
+struct Landing {
+    position: [f64; 3],
+    distance: f64,
+    time: f64,
+    impact_angle: f64,
+    impact_speed: f64
 }
 
+//Pitches closer than this are shown as one solution
+const MERGED_PITCH_EPSILON: f64 = 1e-6;
+
+//Corrections under this many degrees are within what the mount can be set to anyway
+const BARREL_NOTICEABLE: f64 = 0.1;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
 enum MyTabKind {
     Cartesian,
+    Polar,
+    RangeTable,
+    Salvo,
+    FireMission,
+    Forward,
+}
+
+//Which arc to point out when both can hit the target, the other one is dimmed
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Preference {
+    Flat,
+    Lobbed,
+}
+
+//Pause in typing after which a live tab recalculates
+const LIVE_DELAY: Duration = Duration::from_millis(150);
+
+//Undo steps kept per tab, the oldest are dropped past it
+const HISTORY_LIMIT: usize = 50;
+
+//Everything the user can edit on a tab, what undo and redo snapshot and restore
+#[derive(Clone)]
+struct TabInputs {
+    fields: Vec<String>,
+    ammo: Ammo,
+    relative: bool,
+    facing: bool,
+    preference: Preference,
+    calibration_shots: Vec<[String; 5]>,
+    terrain: Vec<[String; 2]>,
+    friendlies: Vec<[String; 2]>,
+    mission_targets: Vec<[String; 3]>
+}
+
+impl PartialEq for TabInputs {
+    //Ammo only compares by id, a calibration keeps the id but changes the values
+    fn eq(&self, other: &Self) -> bool {
+        let ammo = |ammo: &Ammo| (ammo.id(), ammo.drag, ammo.gravity, ammo.velocity_per_charge, ammo.max_charges, ammo.despawn_ticks);
+        self.fields == other.fields && ammo(&self.ammo) == ammo(&other.ammo) && self.relative == other.relative && self.facing == other.facing
+            && self.preference == other.preference && self.calibration_shots == other.calibration_shots && self.terrain == other.terrain
+            && self.friendlies == other.friendlies && self.mission_targets == other.mission_targets
+    }
+}
+
+//Committed states of a tab's inputs, taken once an edit is finished rather than on every keystroke
+#[derive(Clone, Default)]
+struct History {
+    undo: Vec<TabInputs>,
+    redo: Vec<TabInputs>,
+    committed: Option<TabInputs>
+}
+
+impl History {
+    fn commit(&mut self, state: TabInputs) {
+        match &mut self.committed {
+            Some(committed) if *committed == state => {}
+            Some(committed) => {
+                self.undo.push(std::mem::replace(committed, state));
+                if self.undo.len() > HISTORY_LIMIT {
+                    self.undo.remove(0);
+                }
+                self.redo.clear();
+            }
+            //The first state seen is the starting point, there's nothing before it to go back to
+            None => self.committed = Some(state)
+        }
+    }
+
+    fn undo(&mut self) -> Option<TabInputs> {
+        let previous: TabInputs = self.undo.pop()?;
+        self.redo.extend(self.committed.replace(previous.clone()));
+        Some(previous)
+    }
+
+    fn redo(&mut self) -> Option<TabInputs> {
+        let next: TabInputs = self.redo.pop()?;
+        self.undo.extend(self.committed.replace(next.clone()));
+        Some(next)
+    }
+}
+
+//Entries kept in a tab's log, the oldest are dropped past it
+const LOG_LIMIT: usize = 100;
+
+//One calculation attempt as recorded in the tab's log
+#[derive(Clone, PartialEq, Debug)]
+struct LogEntry {
+    time: String,
+    message: String,
+    error: bool
+}
+
+//Wall clock time of day in UTC as HH:MM:SS, enough to tell log entries apart without a date library
+fn clock_time(seconds: u64) -> String {
+    format!("{:02}:{:02}:{:02}", seconds / 3600 % 24, seconds / 60 % 60, seconds % 60)
 }
 
 struct MyTab {
@@ -225,17 +574,75 @@ struct MyTab {
     c_x: String,
     c_y: String,
     c_z: String,
+    relative: bool,
     t_x: String,
     t_y: String,
     t_z: String,
+    v_x: String,
+    v_y: String,
+    v_z: String,
+    distance: String,
+    height: String,
+    azimuth: String,
+    facing: bool,
+    forward: String,
+    right: String,
     ammo_type: Ammo,
+    ammo_filter: String,
+    calibration_shots: Vec<[String; 5]>,
+    calibration: Option<Result<Calibration, String>>,
+    custom_name: String,
+    custom_drag: String,
+    custom_gravity: String,
     charges: String,
+    burst_height: String,
+    table_pitch: String,
+    min_elevation: String,
+    max_elevation: String,
+    barrel_length: String,
+    barrel_blocks: String,
+    obstacle_distance: String,
+    obstacle_height: String,
+    terrain: Vec<[String; 2]>,
+    friendlies: Vec<[String; 2]>,
+    step_size: String,
+    wanted_impact: String,
+    current_yaw: String,
+    current_pitch: String,
     yaw: f64,
     pitch: Pair,
     time: Pair,
     impact_angle: Pair,
-    nozzle_velocity: String, //Remove after calibration
-    drag: String //Remove after calibration
+    impact_speed: Pair,
+    barrel_shift: Pair,
+    analytic_pitch: Pair,
+    snapped_pitch: Pair,
+    sensitive: Pair<bool>,
+    lead: Pair<Option<Lead>>,
+    salvo: Vec<(String, Option<SalvoShot>)>,
+    mission_targets: Vec<[String; 3]>,
+    mission: Vec<Result<SalvoShot, String>>,
+    problems: Vec<String>,
+    log: Vec<LogEntry>,
+    solve_error: Option<SolveError>,
+    solve_info: Option<SolveInfo>,
+    solved: Option<SolveInput>,
+    min_charges: Option<u32>,
+    impact_choice: Option<ImpactChoice>,
+    target_distance: f64,
+    target_height: f64,
+    duplicate_hint_dismissed: bool,
+    pending_paste: Option<(PasteTarget, [String; 3])>,
+    live: bool,
+    vacuum_arc: bool,
+    edited: Option<Instant>,
+    cannon_name: String,
+    name: String,
+    renaming: bool,
+    history: History,
+    preference: Preference,
+    max_range: Option<f64>,
+    solved_state: Option<Vec<String>>
 }
 
 impl MyTab {
@@ -247,23 +654,221 @@ impl MyTab {
             c_x: "".to_string(),
             c_y: "".to_string(),
             c_z: "".to_string(),
+            relative: false,
             t_x: "".to_string(),
             t_y: "".to_string(),
             t_z: "".to_string(),
+            v_x: "".to_string(),
+            v_y: "".to_string(),
+            v_z: "".to_string(),
+            distance: "".to_string(),
+            height: "".to_string(),
+            azimuth: "".to_string(),
+            facing: false,
+            forward: "".to_string(),
+            right: "".to_string(),
             ammo_type: Ammo::shot(),
+            ammo_filter: "".to_string(),
+            calibration_shots: Vec::new(),
+            calibration: None,
+            custom_name: "Custom".to_string(),
+            custom_drag: Ammo::shot().drag.to_string(),
+            custom_gravity: Ammo::shot().gravity.to_string(),
             charges: "1".to_string(),
+            burst_height: "0".to_string(),
+            table_pitch: "45".to_string(),
+            min_elevation: DEFAULT_MIN_ELEVATION.to_string(),
+            max_elevation: DEFAULT_MAX_ELEVATION.to_string(),
+            barrel_length: "0".to_string(),
+            barrel_blocks: "".to_string(),
+            obstacle_distance: "".to_string(),
+            obstacle_height: "".to_string(),
+            terrain: Vec::new(),
+            friendlies: Vec::new(),
+            step_size: "".to_string(),
+            wanted_impact: "".to_string(),
+            current_yaw: "".to_string(),
+            current_pitch: "".to_string(),
             yaw: f64::NAN,
             pitch: Pair {direct_shot: f64::NAN, indirect_shot: f64::NAN},
             time: Pair {direct_shot: f64::NAN, indirect_shot: f64::NAN},
             impact_angle: Pair {direct_shot: f64::NAN, indirect_shot: f64::NAN},
-            nozzle_velocity: "".to_string(), //Remove after calibration
-            drag: "".to_string() //Remove after calibration
+            impact_speed: Pair {direct_shot: f64::NAN, indirect_shot: f64::NAN},
+            barrel_shift: Pair {direct_shot: f64::NAN, indirect_shot: f64::NAN},
+            analytic_pitch: Pair {direct_shot: f64::NAN, indirect_shot: f64::NAN},
+            snapped_pitch: Pair {direct_shot: f64::NAN, indirect_shot: f64::NAN},
+            sensitive: Pair {direct_shot: false, indirect_shot: false},
+            lead: Pair {direct_shot: None, indirect_shot: None},
+            salvo: Vec::new(),
+            mission_targets: Vec::new(),
+            mission: Vec::new(),
+            problems: Vec::new(),
+            log: Vec::new(),
+            solve_error: None,
+            solve_info: None,
+            solved: None,
+            min_charges: None,
+            impact_choice: None,
+            target_distance: f64::NAN,
+            target_height: f64::NAN,
+            duplicate_hint_dismissed: false,
+            pending_paste: None,
+            live: false,
+            vacuum_arc: false,
+            edited: None,
+            cannon_name: "".to_string(),
+            name: "".to_string(),
+            renaming: false,
+            history: History::default(),
+            max_range: None,
+            preference: Preference::Flat,
+            solved_state: None
+        }
+    }
+
+    fn polar(surface: SurfaceIndex, node: NodeIndex) -> Self {
+        Self {
+            kind: MyTabKind::Polar,
+            ..Self::cartesian(surface, node)
+        }
+    }
+
+    //The charges field holds how many charge counts the table lists
+    fn range_table(surface: SurfaceIndex, node: NodeIndex) -> Self {
+        Self {
+            kind: MyTabKind::RangeTable,
+            charges: "8".to_string(),
+            ..Self::cartesian(surface, node)
+        }
+    }
+
+    //Fires every saved cannon at the target, the charges are picked per cannon
+    fn salvo(surface: SurfaceIndex, node: NodeIndex) -> Self {
+        Self {
+            kind: MyTabKind::Salvo,
+            ..Self::cartesian(surface, node)
+        }
+    }
+
+    //Several targets fired at from one cannon with the same ammo, starts with one empty row
+    fn fire_mission(surface: SurfaceIndex, node: NodeIndex) -> Self {
+        Self {
+            kind: MyTabKind::FireMission,
+            mission_targets: vec![Default::default()],
+            ..Self::cartesian(surface, node)
+        }
+    }
+
+    //Where a cannon stuck at one pitch lands, the yaw goes in the azimuth field and the pitch in table_pitch
+    fn forward(surface: SurfaceIndex, node: NodeIndex) -> Self {
+        Self {
+            kind: MyTabKind::Forward,
+            ..Self::cartesian(surface, node)
+        }
+    }
+
+    //Cannon or target X/Y/Z fields a paste writes to
+    fn coordinate_fields(&mut self, target: PasteTarget) -> [&mut String; 3] {
+        match target {
+            PasteTarget::Cannon => [&mut self.c_x, &mut self.c_y, &mut self.c_z],
+            PasteTarget::Target => [&mut self.t_x, &mut self.t_y, &mut self.t_z]
         }
     }
 
-    fn cartesian_tab_content(&mut self, ui: &mut egui::Ui) {
+    fn paste(&mut self, target: PasteTarget, settings: &Settings) {
+        if let Some(pasted) = clipboard_coordinates() {
+            self.paste_values(target, pasted, settings);
+        }
+    }
+
+    //Filled fields are only replaced once the prompt is confirmed, when that's turned on in the settings
+    fn paste_values(&mut self, target: PasteTarget, pasted: [String; 3], settings: &Settings) {
+        let ask: bool = settings.confirm_paste && would_overwrite(&self.coordinate_fields(target));
+        self.pending_paste = Some((target, pasted));
+        if !ask {
+            self.resolve_paste(true);
+        }
+    }
+
+    fn resolve_paste(&mut self, confirmed: bool) {
+        if let Some((target, pasted)) = self.pending_paste.take().filter(|_| confirmed) {
+            for (field, value) in self.coordinate_fields(target).into_iter().zip(pasted) {
+                *field = value;
+            }
+        }
+    }
+
+    fn paste_prompt(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        let Some((target, pasted)) = &self.pending_paste else { return };
+        let question = if *target == PasteTarget::Cannon { Text::ReplaceCannon } else { Text::ReplaceTarget };
+        let text: String = format!("{} {}?", question.get(settings.language), pasted.join(" / "));
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(text).color(ui.visuals().warn_fg_color));
+            if ui.small_button(Text::Replace.get(settings.language)).clicked() {
+                self.resolve_paste(true);
+            }
+            if ui.small_button(Text::Cancel.get(settings.language)).clicked() {
+                self.resolve_paste(false);
+            }
+        });
+    }
+
+    //New tab in the given leaf with the same inputs, results are left to be calculated again
+    fn duplicate(&self, surface: SurfaceIndex, node: NodeIndex) -> Self {
+        Self {
+            kind: self.kind,
+            c_x: self.c_x.clone(),
+            c_y: self.c_y.clone(),
+            c_z: self.c_z.clone(),
+            relative: self.relative,
+            t_x: self.t_x.clone(),
+            t_y: self.t_y.clone(),
+            t_z: self.t_z.clone(),
+            v_x: self.v_x.clone(),
+            v_y: self.v_y.clone(),
+            v_z: self.v_z.clone(),
+            distance: self.distance.clone(),
+            height: self.height.clone(),
+            azimuth: self.azimuth.clone(),
+            facing: self.facing,
+            forward: self.forward.clone(),
+            right: self.right.clone(),
+            ammo_type: self.ammo_type.clone(),
+            calibration_shots: self.calibration_shots.clone(),
+            custom_name: self.custom_name.clone(),
+            custom_drag: self.custom_drag.clone(),
+            custom_gravity: self.custom_gravity.clone(),
+            charges: self.charges.clone(),
+            burst_height: self.burst_height.clone(),
+            table_pitch: self.table_pitch.clone(),
+            min_elevation: self.min_elevation.clone(),
+            max_elevation: self.max_elevation.clone(),
+            barrel_length: self.barrel_length.clone(),
+            barrel_blocks: self.barrel_blocks.clone(),
+            obstacle_distance: self.obstacle_distance.clone(),
+            obstacle_height: self.obstacle_height.clone(),
+            terrain: self.terrain.clone(),
+            friendlies: self.friendlies.clone(),
+            step_size: self.step_size.clone(),
+            wanted_impact: self.wanted_impact.clone(),
+            current_yaw: self.current_yaw.clone(),
+            current_pitch: self.current_pitch.clone(),
+            mission_targets: self.mission_targets.clone(),
+            live: self.live,
+            vacuum_arc: self.vacuum_arc,
+            preference: self.preference,
+            ..Self::cartesian(surface, node)
+        }
+    }
+
+    fn cartesian_tab_content(&mut self, ui: &mut egui::Ui, settings: &mut Settings) {
+        let mut submitted = false;
+        let inputs: Vec<String> = self.input_state();
+        let focused: Option<egui::Id> = ui.memory(|m| m.focused());
+        let mut focus_chain: Vec<egui::Id> = Vec::new();
+
         ui.vertical_centered(|ui| {
-            ui.label(RichText::new("Cartesian").size(30.0));
+            ui.label(RichText::new(Text::Cartesian.get(settings.language)).size(30.0));
         });
 
         //Fields for cannon and target coords
@@ -279,28 +884,41 @@ impl MyTab {
                 .min_row_height(15.0)
                 .show(ui, |ui| {
                     ui.label("");
-                    ui.label(RichText::new(" Cannon").size(TITLE_TEXT));
+                    ui.label(RichText::new(format!(" {}", Text::Cannon.get(settings.language))).size(TITLE_TEXT));
                     ui.end_row();
 
-                    ui.label(RichText::new("X: ").size(NORMAL_TEXT));
-                    if ui.text_edit_singleline(&mut self.c_x).changed() {
-                        verify_signed_float_input(&mut self.c_x);
-                    }
+                    //In relative mode the cannon is the origin, its fields are only hidden so they survive toggling back
+                    ui.label("");
+                    ui.checkbox(&mut self.relative, RichText::new(Text::AtOrigin.get(settings.language)).size(NORMAL_TEXT));
 
-                    ui.end_row();
-                    ui.label(RichText::new("Y: ").size(NORMAL_TEXT));
-                    if ui.text_edit_singleline(&mut self.c_y).changed() {
-                        verify_signed_float_input(&mut self.c_y);
-                    }
+                    if !self.relative {
+                        let cannon_tip: &str = Text::CannonTip.get(settings.language);
+                        ui.end_row();
+                        ui.label(RichText::new("X: ").size(NORMAL_TEXT));
+                        submitted |= enter_pressed(&coordinate_field(ui, &mut self.c_x, settings.steppers, settings.language, &mut focus_chain).on_hover_text(cannon_tip));
 
-                    ui.end_row();
-                    ui.label(RichText::new("Z: ").size(NORMAL_TEXT));
-                    if ui.text_edit_singleline(&mut self.c_z).changed() {
-                        verify_signed_float_input(&mut self.c_z);
+                        ui.end_row();
+                        ui.label(RichText::new("Y: ").size(NORMAL_TEXT));
+                        submitted |= enter_pressed(&coordinate_field(ui, &mut self.c_y, settings.steppers, settings.language, &mut focus_chain).on_hover_text(cannon_tip));
+
+                        ui.end_row();
+                        ui.label(RichText::new("Z: ").size(NORMAL_TEXT));
+                        submitted |= enter_pressed(&coordinate_field(ui, &mut self.c_z, settings.steppers, settings.language, &mut focus_chain).on_hover_text(cannon_tip));
                     }
                     ui.end_row();
                     ui.label(RichText::new("  ").size(NORMAL_TEXT));
                 });
+                if let Some(hint) = settings.world_height_hint(&self.c_y).filter(|_| !self.relative) {
+                    ui.label(RichText::new(hint).color(ui.visuals().warn_fg_color));
+                }
+                if !self.relative {
+                    ui.horizontal(|ui| {
+                        if ui.small_button(Text::PasteCoords.get(settings.language)).clicked() {
+                            self.paste(PasteTarget::Cannon, settings);
+                        }
+                        self.cannons_menu(ui, settings);
+                    });
+                }
             });
             ui.vertical(|ui| {
                 Grid::new("target-info")
@@ -308,300 +926,4722 @@ impl MyTab {
                 .max_col_width(80.0)
                 .show(ui, |ui| {
                     ui.label("");
-                    ui.label(RichText::new(" Target").size(TITLE_TEXT));
+                    let title = if self.relative { Text::TargetOffset } else { Text::Target };
+                    ui.label(RichText::new(format!(" {}", title.get(settings.language))).size(TITLE_TEXT));
                     ui.end_row();
 
+                    let target_tip: &str = if self.relative { Text::TargetOffsetTip } else { Text::TargetTip }.get(settings.language);
                     ui.label(RichText::new("X: ").size(NORMAL_TEXT));
-                    if ui.text_edit_singleline(&mut self.t_x).changed() {
-                        verify_signed_float_input(&mut self.t_x);
-                    }
+                    submitted |= enter_pressed(&coordinate_field(ui, &mut self.t_x, settings.steppers, settings.language, &mut focus_chain).on_hover_text(target_tip));
 
                     ui.end_row();
                     ui.label(RichText::new("Y: ").size(NORMAL_TEXT));
-                    if ui.text_edit_singleline(&mut self.t_y).changed() {
-                        verify_signed_float_input(&mut self.t_y);
-                    }
+                    submitted |= enter_pressed(&coordinate_field(ui, &mut self.t_y, settings.steppers, settings.language, &mut focus_chain).on_hover_text(target_tip));
 
                     ui.end_row();
                     ui.label(RichText::new("Z: ").size(NORMAL_TEXT));
-                    if ui.text_edit_singleline(&mut self.t_z).changed() {
-                        verify_signed_float_input(&mut self.t_z);
-                    }
+                    submitted |= enter_pressed(&coordinate_field(ui, &mut self.t_z, settings.steppers, settings.language, &mut focus_chain).on_hover_text(target_tip));
                 });
-            });
-        });
-        
-        //Ammo type selector and number of powder charges
-        ui.horizontal(|ui| {
-            ComboBox::new("Ammo type", RichText::new(" :Ammo type").size(NORMAL_TEXT))
-            .selected_text(RichText::new(format!("{}", self.ammo_type.name)).size(NORMAL_TEXT))
-            .show_ui(ui, |ui| {
-                for ammo_type in ["Shot", "AP Shot", "AP Shell", "HE Shell", "Mortar Stone", "Smoke Shell"] {
-                    ui.selectable_value(
-                        &mut self.ammo_type,
-                        Ammo::select(ammo_type),
-                        RichText::new(ammo_type).size(NORMAL_TEXT)
-                    );
+                //An offset isn't a world position, so relative targets aren't checked
+                if let Some(hint) = settings.world_height_hint(&self.t_y).filter(|_| !self.relative) {
+                    ui.label(RichText::new(hint).color(ui.visuals().warn_fg_color));
+                }
+                if ui.small_button(Text::PasteCoords.get(settings.language)).clicked() {
+                    self.paste(PasteTarget::Target, settings);
                 }
             });
+        });
 
-            ui.add_space(10.0);
+        self.paste_prompt(ui, settings);
 
-            Grid::new("charges")
-            .max_col_width(30.0)
+        //Low-key hint for a likely copy/paste typo, can be dismissed per tab
+        if !self.duplicate_hint_dismissed {
+            let cannon_duplicate = !self.relative && has_duplicate_coordinate(&self.c_x, &self.c_y, &self.c_z);
+            let target_duplicate = has_duplicate_coordinate(&self.t_x, &self.t_y, &self.t_z);
+            if cannon_duplicate || target_duplicate {
+                let hint = match (cannon_duplicate, target_duplicate) {
+                    (true, true) => Text::DuplicateBoth,
+                    (true, false) => Text::DuplicateCannon,
+                    _ => Text::DuplicateTarget,
+                };
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(hint.get(settings.language)).weak());
+                    if ui.small_button(Text::Dismiss.get(settings.language)).clicked() {
+                        self.duplicate_hint_dismissed = true;
+                    }
+                });
+            }
+        }
+
+        //Optional velocity of a moving target, used to lead the shot
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("{} ({}/s): ", Text::TargetVelocity.get(settings.language), Text::Blocks.get(settings.language))).size(NORMAL_TEXT));
+            Grid::new("target-velocity")
+            .max_col_width(50.0)
             .show(ui, |ui| {
-                if ui.text_edit_singleline(&mut self.charges).changed() {
-                    verify_positive_integer_input(&mut self.charges);
-                }
+                ui.label(RichText::new("X: ").size(NORMAL_TEXT));
+                submitted |= enter_pressed(&input_field(ui, &mut self.v_x, verify_signed_float_input, settings.language));
+                ui.label(RichText::new("Y: ").size(NORMAL_TEXT));
+                submitted |= enter_pressed(&input_field(ui, &mut self.v_y, verify_signed_float_input, settings.language));
+                ui.label(RichText::new("Z: ").size(NORMAL_TEXT));
+                submitted |= enter_pressed(&input_field(ui, &mut self.v_z, verify_signed_float_input, settings.language));
             });
+        });
+        
+        submitted |= self.ammo_content(ui, settings, &mut focus_chain);
+        self.calibration_content(ui, settings);
+        submitted |= self.elevation_content(ui, settings);
+        submitted |= self.impact_goal_content(ui, settings);
+        self.obstacle_content(ui, settings);
+        self.terrain_content(ui, settings);
+        self.friendlies_content(ui, settings);
+        self.current_aim_content(ui, settings);
 
-            ui.label(RichText::new(" :Powder charges").size(NORMAL_TEXT));
+        self.calculate_content(ui, settings, submitted, inputs, &mut focus_chain);
+        follow_focus_chain(ui, &focus_chain, focused);
 
-            //Remove after calibration
-            Grid::new("velocity")
-            .max_col_width(30.0)
-            .show(ui, |ui| {
-                if ui.text_edit_singleline(&mut self.nozzle_velocity).changed() {
-                    verify_signed_float_input(&mut self.nozzle_velocity);
-                }
-            });
-            ui.label(RichText::new(" :Nozzle velocity").size(NORMAL_TEXT));
+        self.results_content(ui, settings);
+    }
 
-            Grid::new("velocity")
-            .max_col_width(30.0)
-            .show(ui, |ui| {
-                if ui.text_edit_singleline(&mut self.drag).changed() {
-                    verify_signed_float_input(&mut self.drag);
-                }
-            });
-            ui.label(RichText::new(" :Drag").size(NORMAL_TEXT));
+    fn polar_tab_content(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        let mut submitted = false;
+        let inputs: Vec<String> = self.input_state();
+        let focused: Option<egui::Id> = ui.memory(|m| m.focused());
+        let mut focus_chain: Vec<egui::Id> = Vec::new();
 
+        ui.vertical_centered(|ui| {
+            ui.label(RichText::new(Text::Polar.get(settings.language)).size(30.0));
         });
 
-        if ui.button(RichText::new("Calculate").size(TITLE_TEXT)).clicked() {
-            let mut x: f64 = 0.0;
-            let mut y: f64 = 0.0;
-            let mut z: f64 = 0.0;
+        //Target either as distance, height and azimuth, or as offsets from where the player is facing
+        ui.checkbox(&mut self.facing, RichText::new(Text::RelativeToFacing.get(settings.language)).size(NORMAL_TEXT));
+        Grid::new("polar-info")
+        .min_col_width(10.0)
+        .max_col_width(120.0)
+        .min_row_height(15.0)
+        .show(ui, |ui| {
+            //Only the apex needs it, to compare against the build height
+            ui.label(RichText::new(format!("{} Y: ", Text::Cannon.get(settings.language))).size(NORMAL_TEXT));
+            submitted |= enter_pressed(&chained_field(ui, &mut self.c_y, verify_signed_float_input, settings.language, &mut focus_chain));
+            ui.end_row();
+
+            if self.facing {
+                ui.label(RichText::new(format!("{} ({}): ", Text::FacingYaw.get(settings.language), settings.angle_unit.symbol().trim())).size(NORMAL_TEXT));
+                submitted |= enter_pressed(&chained_field(ui, &mut self.azimuth, verify_signed_float_input, settings.language, &mut focus_chain));
+                ui.end_row();
 
-            //Convert input coords of cannon and target to f64 and store the difference
+                ui.label(RichText::new(format!("{}: ", Text::Forward.get(settings.language))).size(NORMAL_TEXT));
+                submitted |= enter_pressed(&chained_field(ui, &mut self.forward, verify_signed_float_input, settings.language, &mut focus_chain));
+                ui.end_row();
 
-            match self.t_x.parse::<f64>() {
-                Ok(t_x) => x += t_x,
-                Err(_) => {}
-            }
-            match self.c_x.parse::<f64>() {
-                Ok(t_x) => x -= t_x,
-                Err(_) => {}
-            }
+                ui.label(RichText::new(format!("{}: ", Text::Right.get(settings.language))).size(NORMAL_TEXT));
+                submitted |= enter_pressed(&chained_field(ui, &mut self.right, verify_signed_float_input, settings.language, &mut focus_chain));
+                ui.end_row();
 
-            match self.t_y.parse::<f64>() {
-                Ok(t_y) => y += t_y,
-                Err(_) => {}
-            }
-            match self.c_y.parse::<f64>() {
-                Ok(t_y) => y -= t_y,
-                Err(_) => {}
+                ui.label(RichText::new(format!("{}: ", Text::Up.get(settings.language))).size(NORMAL_TEXT));
+                submitted |= enter_pressed(&chained_field(ui, &mut self.height, verify_signed_float_input, settings.language, &mut focus_chain));
+                ui.end_row();
+                return;
             }
+            ui.label(RichText::new(format!("{}: ", Text::Distance.get(settings.language))).size(NORMAL_TEXT));
+            submitted |= enter_pressed(&chained_field(ui, &mut self.distance, verify_signed_float_input, settings.language, &mut focus_chain));
+            ui.end_row();
 
-            match self.t_z.parse::<f64>() {
-                Ok(t_z) => z += t_z,
-                Err(_) => {}
-            }
-            match self.c_z.parse::<f64>() {
-                Ok(t_z) => z -= t_z,
-                Err(_) => {}
-            }
+            ui.label(RichText::new(format!("{}: ", Text::Height.get(settings.language))).size(NORMAL_TEXT));
+            submitted |= enter_pressed(&chained_field(ui, &mut self.height, verify_signed_float_input, settings.language, &mut focus_chain));
+            ui.end_row();
+
+            ui.label(RichText::new(format!("Azimuth ({}): ", settings.angle_unit.symbol().trim())).size(NORMAL_TEXT));
+            submitted |= enter_pressed(&chained_field(ui, &mut self.azimuth, verify_signed_float_input, settings.language, &mut focus_chain));
+            ui.end_row();
+        });
 
-            self.yaw = calc_yaw(x, z);
+        submitted |= self.ammo_content(ui, settings, &mut focus_chain);
+        submitted |= self.elevation_content(ui, settings);
+        submitted |= self.impact_goal_content(ui, settings);
+        self.obstacle_content(ui, settings);
+        self.terrain_content(ui, settings);
+        self.friendlies_content(ui, settings);
+        self.current_aim_content(ui, settings);
 
-            //TO-DO: Implement usage of ammo type and ammount of power charges, calibratrion required
-            
-            //Remove after calibration
-            let mut v: f64 = f64::NAN;
-            match self.nozzle_velocity.parse::<f64>() {
-                Ok(nozzle_velocity) => v = nozzle_velocity,
-                Err(_) => {}
-            }
+        self.calculate_content(ui, settings, submitted, inputs, &mut focus_chain);
+        follow_focus_chain(ui, &focus_chain, focused);
 
-            let mut u: f64 = f64::NAN;
-            match self.drag.parse::<f64>() {
-                Ok(drag) => u = drag,
-                Err(_) => {}
-            }
+        self.results_content(ui, settings);
+    }
 
-            let d: f64 = (x*x + z*z).sqrt();
+    fn salvo_content(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        let mut submitted = false;
+        let inputs: Vec<String> = self.input_state();
+        let focused: Option<egui::Id> = ui.memory(|m| m.focused());
+        let mut focus_chain: Vec<egui::Id> = Vec::new();
 
-            let critical_point = find_critical_point(d, u, v, self.ammo_type.gravity);
-            let angles = find_angles(d, y, u, v, self.ammo_type.gravity, critical_point);
+        ui.vertical_centered(|ui| {
+            ui.label(RichText::new(Text::Salvo.get(settings.language)).size(30.0));
+        });
 
-            match angles {
-                Ok(angles) => {
-                    self.pitch.direct_shot = angles.0;
-                    self.pitch.indirect_shot = angles.1;
-                }
-                _ => {
-                    self.pitch.direct_shot = f64::NAN;
-                    self.pitch.indirect_shot = f64::NAN;
-                }
+        ui.horizontal(|ui| {
+            Grid::new("salvo-target")
+            .max_col_width(80.0)
+            .show(ui, |ui| {
+                ui.label(RichText::new(format!("{} X: ", Text::Target.get(settings.language))).size(NORMAL_TEXT));
+                submitted |= enter_pressed(&coordinate_field(ui, &mut self.t_x, settings.steppers, settings.language, &mut focus_chain));
+                ui.label(RichText::new("Y: ").size(NORMAL_TEXT));
+                submitted |= enter_pressed(&coordinate_field(ui, &mut self.t_y, settings.steppers, settings.language, &mut focus_chain));
+                ui.label(RichText::new("Z: ").size(NORMAL_TEXT));
+                submitted |= enter_pressed(&coordinate_field(ui, &mut self.t_z, settings.steppers, settings.language, &mut focus_chain));
+            });
+            if ui.small_button(Text::PasteCoords.get(settings.language)).clicked() {
+                self.paste(PasteTarget::Target, settings);
             }
+        });
+        self.paste_prompt(ui, settings);
+
+        self.ammo_selector(ui, settings);
+        submitted |= self.custom_ammo_content(ui, settings);
+        submitted |= self.elevation_content(ui, settings);
+
+        self.calculate_content(ui, settings, submitted, inputs, &mut focus_chain);
+        follow_focus_chain(ui, &focus_chain, focused);
+
+        if settings.cannons.is_empty() {
+            ui.label(RichText::new(Text::NoCannons.get(settings.language)).weak());
+            return;
+        }
+        if self.problems_content(ui) || self.salvo.is_empty() {
+            return;
         }
 
-        //Show results
-        Grid::new("results")
-        .min_col_width(ui.available_width() / 2.0)
-        .max_col_width(ui.available_width() / 2.0)
+        let impact: f64 = salvo_impact_time(self.salvo.iter().filter_map(|(_, shot)| shot.as_ref()));
+        let decimals: usize = settings.decimals;
+        Grid::new("salvo-table")
+        .striped(true)
+        .min_col_width(60.0)
         .show(ui, |ui| {
-            ui.vertical(|ui| {
-                ui.group(|ui| {
-                    ui.label(RichText::new("Direct Shot     ").size(NORMAL_TEXT * (4.0/3.0)));
-                    ui.label(RichText::new(format!("Yaw: {:.4}°", self.yaw.to_degrees())).size(NORMAL_TEXT));
-                    if self.pitch.direct_shot.is_finite() {
-                        ui.label(RichText::new(format!("Pitch: {}°", self.pitch.direct_shot.to_degrees())).size(NORMAL_TEXT));
-                        ui.label(RichText::new(format!("Flight time: {:.4}s", self.time.direct_shot)).size(NORMAL_TEXT));
-                        ui.label(RichText::new(format!("Impact angle: {:.4}°", self.impact_angle.direct_shot.to_degrees())).size(NORMAL_TEXT));
-                    } else {
-                        ui.label(RichText::new("OUT OF RANGE").size(NORMAL_TEXT * (4.0/3.0)));
+            for header in [Text::Cannon, Text::Yaw, Text::Pitch, Text::Charges, Text::FlightTime, Text::FireAt] {
+                ui.label(RichText::new(header.get(settings.language)).size(NORMAL_TEXT));
+            }
+            ui.end_row();
+
+            for (name, shot) in &self.salvo {
+                ui.label(RichText::new(name).size(NORMAL_TEXT));
+                match shot {
+                    Some(shot) => {
+                        ui.label(RichText::new(settings.angle_unit.format(shot.yaw, decimals)).size(NORMAL_TEXT));
+                        ui.label(RichText::new(settings.angle_unit.format(shot.pitch, decimals)).size(NORMAL_TEXT));
+                        ui.label(RichText::new(shot.charges.to_string()).size(NORMAL_TEXT));
+                        ui.label(RichText::new(format!("{:.*}s", decimals, shot.time)).size(NORMAL_TEXT));
+                        ui.label(RichText::new(format!("{:.*}s", decimals, impact - shot.time)).size(NORMAL_TEXT));
                     }
-                });
-            });
-            ui.vertical(|ui| {
-                ui.group(|ui| {
-                    ui.label(RichText::new("Indirect Shot   ").size(NORMAL_TEXT * (4.0/3.0)));
-                    ui.label(RichText::new(format!("Yaw: {:.4}°", self.yaw.to_degrees())).size(NORMAL_TEXT));
-                    if self.pitch.direct_shot.is_finite() {
-                        ui.label(RichText::new(format!("Pitch: {}°", self.pitch.indirect_shot.to_degrees())).size(NORMAL_TEXT));
-                        ui.label(RichText::new(format!("Flight time: {:.4}s", self.time.indirect_shot)).size(NORMAL_TEXT));
-                        ui.label(RichText::new(format!("Impact angle: {:.4}°", self.impact_angle.indirect_shot.to_degrees())).size(NORMAL_TEXT));
-                    } else {
-                        ui.label(RichText::new("OUT OF RANGE").size(NORMAL_TEXT * (4.0/3.0)));
+                    None => {
+                        ui.label(RichText::new(Text::OutOfRange.get(settings.language)).size(NORMAL_TEXT).color(ui.visuals().error_fg_color));
                     }
-                });
-            });
+                }
+                ui.end_row();
+            }
         });
+        if impact.is_finite() {
+            ui.label(RichText::new(format!("{} {:.*}s {}", Text::ImpactAfter.get(settings.language), decimals, impact, Text::AfterFirstShot.get(settings.language))).size(NORMAL_TEXT));
+        }
     }
 
-    fn title(&self) -> String {
-        match self.kind {
-            MyTabKind::Cartesian => format!("Cartesian Tab {}", self.node.0),
+    fn fire_mission_content(&mut self, ui: &mut egui::Ui, settings: &mut Settings) {
+        let mut submitted = false;
+        let inputs: Vec<String> = self.input_state();
+        let focused: Option<egui::Id> = ui.memory(|m| m.focused());
+        let mut focus_chain: Vec<egui::Id> = Vec::new();
+
+        ui.vertical_centered(|ui| {
+            ui.label(RichText::new(Text::FireMission.get(settings.language)).size(30.0));
+        });
+
+        //One firing point for every target, usually picked from the saved cannons
+        ui.horizontal(|ui| {
+            Grid::new("mission-cannon")
+            .max_col_width(80.0)
+            .show(ui, |ui| {
+                ui.label(RichText::new(format!("{} X: ", Text::Cannon.get(settings.language))).size(NORMAL_TEXT));
+                submitted |= enter_pressed(&coordinate_field(ui, &mut self.c_x, settings.steppers, settings.language, &mut focus_chain));
+                ui.label(RichText::new("Y: ").size(NORMAL_TEXT));
+                submitted |= enter_pressed(&coordinate_field(ui, &mut self.c_y, settings.steppers, settings.language, &mut focus_chain));
+                ui.label(RichText::new("Z: ").size(NORMAL_TEXT));
+                submitted |= enter_pressed(&coordinate_field(ui, &mut self.c_z, settings.steppers, settings.language, &mut focus_chain));
+            });
+            if ui.small_button(Text::PasteCoords.get(settings.language)).clicked() {
+                self.paste(PasteTarget::Cannon, settings);
+            }
+            self.cannons_menu(ui, settings);
+        });
+        self.paste_prompt(ui, settings);
+
+        let mut removed: Option<usize> = None;
+        Grid::new("mission-targets")
+        .max_col_width(80.0)
+        .show(ui, |ui| {
+            for (i, target) in self.mission_targets.iter_mut().enumerate() {
+                ui.label(RichText::new(format!("{} {}  X: ", Text::Target.get(settings.language), i + 1)).size(NORMAL_TEXT));
+                submitted |= enter_pressed(&coordinate_field(ui, &mut target[0], settings.steppers, settings.language, &mut focus_chain));
+                ui.label(RichText::new("Y: ").size(NORMAL_TEXT));
+                submitted |= enter_pressed(&coordinate_field(ui, &mut target[1], settings.steppers, settings.language, &mut focus_chain));
+                ui.label(RichText::new("Z: ").size(NORMAL_TEXT));
+                submitted |= enter_pressed(&coordinate_field(ui, &mut target[2], settings.steppers, settings.language, &mut focus_chain));
+                if ui.small_button("✖").on_hover_text(Text::RemoveTarget.get(settings.language)).clicked() {
+                    removed = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(i) = removed {
+            self.mission_targets.remove(i);
+            self.mission.clear();
+        }
+        ui.horizontal(|ui| {
+            if ui.button(Text::AddTarget.get(settings.language)).clicked() {
+                self.mission_targets.push(Default::default());
+            }
+            if ui.button(Text::PasteNewTarget.get(settings.language)).clicked() {
+                if let Some(target) = clipboard_coordinates() {
+                    self.mission_targets.push(target);
+                }
+            }
+        });
+
+        submitted |= self.ammo_content(ui, settings, &mut focus_chain);
+        submitted |= self.elevation_content(ui, settings);
+
+        self.calculate_content(ui, settings, submitted, inputs, &mut focus_chain);
+        follow_focus_chain(ui, &focus_chain, focused);
+
+        if self.problems_content(ui) || self.mission.is_empty() {
+            return;
         }
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("{}: ", Text::Prefer.get(settings.language))).size(NORMAL_TEXT));
+            let flat = ui.radio_value(&mut self.preference, Preference::Flat, Text::Flat.get(settings.language));
+            let lobbed = ui.radio_value(&mut self.preference, Preference::Lobbed, Text::Lobbed.get(settings.language));
+            //The rows hold one arc each, so switching it solves them again
+            if flat.changed() || lobbed.changed() {
+                self.calculate(settings);
+            }
+        });
+
+        let decimals: usize = settings.decimals;
+        Grid::new("mission-table")
+        .striped(true)
+        .min_col_width(60.0)
+        .show(ui, |ui| {
+            for header in [Text::Target, Text::Yaw, Text::Pitch, Text::Charges, Text::FlightTime] {
+                ui.label(RichText::new(header.get(settings.language)).size(NORMAL_TEXT));
+            }
+            ui.end_row();
+
+            for (i, shot) in self.mission.iter().enumerate() {
+                ui.label(RichText::new((i + 1).to_string()).size(NORMAL_TEXT));
+                match shot {
+                    Ok(shot) => {
+                        ui.label(RichText::new(settings.angle_unit.format(shot.yaw, decimals)).size(NORMAL_TEXT));
+                        ui.label(RichText::new(settings.angle_unit.format(shot.pitch, decimals)).size(NORMAL_TEXT));
+                        ui.label(RichText::new(shot.charges.to_string()).size(NORMAL_TEXT));
+                        ui.label(RichText::new(format!("{:.*}s", decimals, shot.time)).size(NORMAL_TEXT));
+                    }
+                    Err(problem) => {
+                        ui.label(RichText::new(problem).size(NORMAL_TEXT).color(ui.visuals().error_fg_color));
+                    }
+                }
+                ui.end_row();
+            }
+        });
     }
-}
-struct TabViewer<'a> {
-    added_nodes: &'a mut Vec<MyTab>,
-}
 
-impl egui_dock::TabViewer for TabViewer<'_> {
-    type Tab = MyTab;
+    //The other way round: where the shot lands for the pitch the cannon is set to
+    fn forward_content(&mut self, ui: &mut egui::Ui, settings: &mut Settings) {
+        let focused: Option<egui::Id> = ui.memory(|m| m.focused());
+        let mut focus_chain: Vec<egui::Id> = Vec::new();
 
-    fn title(&mut self, tab: &mut Self::Tab) -> egui_dock::egui::WidgetText {
-        tab.title().into()
+        ui.vertical_centered(|ui| {
+            ui.label(RichText::new(Text::FixedPitch.get(settings.language)).size(30.0));
+        });
+
+        ui.horizontal(|ui| {
+            Grid::new("forward-cannon")
+            .max_col_width(80.0)
+            .show(ui, |ui| {
+                ui.label(RichText::new(format!("{} X: ", Text::Cannon.get(settings.language))).size(NORMAL_TEXT));
+                coordinate_field(ui, &mut self.c_x, settings.steppers, settings.language, &mut focus_chain);
+                ui.label(RichText::new("Y: ").size(NORMAL_TEXT));
+                coordinate_field(ui, &mut self.c_y, settings.steppers, settings.language, &mut focus_chain);
+                ui.label(RichText::new("Z: ").size(NORMAL_TEXT));
+                coordinate_field(ui, &mut self.c_z, settings.steppers, settings.language, &mut focus_chain);
+            });
+            if ui.small_button(Text::PasteCoords.get(settings.language)).clicked() {
+                self.paste(PasteTarget::Cannon, settings);
+            }
+            self.cannons_menu(ui, settings);
+        });
+        self.paste_prompt(ui, settings);
+
+        Grid::new("forward-aim")
+        .max_col_width(40.0)
+        .show(ui, |ui| {
+            input_field(ui, &mut self.azimuth, verify_signed_float_input, settings.language);
+            ui.label(RichText::new(format!("{} :{}", settings.angle_unit.symbol(), Text::Yaw.get(settings.language))).size(NORMAL_TEXT));
+            input_field(ui, &mut self.table_pitch, verify_signed_float_input, settings.language);
+            ui.label(RichText::new(format!("{} :{}", settings.angle_unit.symbol(), Text::Pitch.get(settings.language))).size(NORMAL_TEXT));
+            input_field(ui, &mut self.t_y, verify_signed_float_input, settings.language);
+            ui.label(RichText::new(format!(" :{}", Text::FixedTargetY.get(settings.language))).size(NORMAL_TEXT));
+        });
+
+        //Nothing is solved ahead of time here, this only keeps the max range readout current
+        self.calculate(settings);
+        self.ammo_content(ui, settings, &mut focus_chain);
+        follow_focus_chain(ui, &focus_chain, focused);
+        ui.separator();
+
+        let landing: Landing = match self.landing(settings) {
+            Ok(landing) => landing,
+            Err(problem) => {
+                ui.label(RichText::new(problem).size(NORMAL_TEXT * (4.0/3.0)).color(ui.visuals().error_fg_color));
+                return;
+            }
+        };
+        let [x, y, z] = landing.position;
+        ui.label(RichText::new(format!("{} {:.1}, {:.1}, {:.1}", Text::LandsAt.get(settings.language), x, y, z)).size(NORMAL_TEXT * (4.0/3.0)));
+        ui.label(RichText::new(format!("{:.1} {} {}", landing.distance, Text::Blocks.get(settings.language), Text::Away.get(settings.language))).size(NORMAL_TEXT));
+        ui.label(RichText::new(format!("{}: {:.*}s ({} {})", Text::FlightTime.get(settings.language), settings.decimals, landing.time, seconds_to_ticks(landing.time), Text::Ticks.get(settings.language))).size(NORMAL_TEXT));
+        if self.ammo_type.despawns(landing.time) {
+            ui.label(RichText::new(Text::Despawns.get(settings.language)).size(NORMAL_TEXT).color(ui.visuals().error_fg_color));
+        }
+        ui.label(RichText::new(format!("{}: {}", Text::ImpactAngle.get(settings.language), settings.angle_unit.format(landing.impact_angle, settings.decimals))).size(NORMAL_TEXT));
+        ui.label(RichText::new(format!("{}: {:.1} {}/s", Text::ImpactSpeed.get(settings.language), landing.impact_speed, Text::Blocks.get(settings.language))).size(NORMAL_TEXT));
     }
 
-    fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {
-        tab.cartesian_tab_content(ui);
+    //Follows the fixed-pitch shot down to the target Y, on the way down like a shell falling onto a block
+    fn landing(&self, settings: &Settings) -> Result<Landing, String> {
+        let pitch: f64 = settings.angle_unit.to_radians(optional_f64(&self.table_pitch, f64::NAN));
+        if !pitch.is_finite() {
+            return Err(Text::EnterValidPitch.get(settings.language).to_string());
+        }
+        let charges: u32 = self.charges.parse().unwrap_or(0);
+        if charges == 0 {
+            return Err(Text::InvalidCharges.get(settings.language).to_string());
+        }
+        let u: f64 = self.ammo_type.drag;
+        let g: f64 = self.ammo_type.gravity * settings.gravity_multiplier;
+        if !(u.is_finite() && g.is_finite() && u >= 0.0 && g > 0.0) {
+            return Err(Text::InvalidAmmo.get(settings.language).to_string());
+        }
+        let cannon: [f64; 3] = self.cannon();
+        let target_y: f64 = optional_f64(&self.t_y, cannon[1]);
+        if target_y.is_nan() {
+            return Err(format!("{} Y {}", Text::Target.get(settings.language), Text::NotANumber.get(settings.language)));
+        }
+
+        let v: f64 = self.ammo_type.muzzle_velocity(charges, self.barrel_blocks());
+        let distance: f64 = landing_distance(target_y - cannon[1], u, v, g, pitch)
+            .ok_or_else(|| format!("{} Y {}", Text::NeverComesDown.get(settings.language), target_y))?;
+        let time: f64 = flight_time(distance, u, v, pitch);
+        let (x, z) = facing_offset(settings.angle_unit.heading(optional_f64(&self.azimuth, 0.0)), distance, 0.0);
+        Ok(Landing {
+            position: [cannon[0] + x, target_y, cannon[2] + z],
+            distance,
+            time,
+            impact_angle: impact_angle(u, v, g, pitch, time),
+            impact_speed: impact_speed(u, v, g, pitch, time)
+        })
     }
 
-    fn add_popup(&mut self, ui: &mut egui::Ui, surface: SurfaceIndex, node: NodeIndex) {
-        ui.set_min_width(80.0);
-        ui.style_mut().visuals.button_frame = false;
+    fn range_table_content(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        ui.vertical_centered(|ui| {
+            ui.label(RichText::new(Text::RangeTable.get(settings.language)).size(30.0));
+        });
 
-        if ui.button("Cartesian tab").clicked() {
-            self.added_nodes.push(MyTab::cartesian(surface, node));
+        ui.horizontal(|ui| {
+            self.ammo_selector(ui, settings);
+
+            ui.add_space(10.0);
+
+            Grid::new("table-inputs")
+            .max_col_width(40.0)
+            .show(ui, |ui| {
+                input_field(ui, &mut self.table_pitch, verify_signed_float_input, settings.language);
+                ui.label(RichText::new(format!("{} :{}", settings.angle_unit.symbol(), Text::Pitch.get(settings.language))).size(NORMAL_TEXT));
+                input_field(ui, &mut self.charges, verify_positive_integer_input, settings.language);
+                ui.label(RichText::new(format!(" :{}", Text::UpToCharges.get(settings.language))).size(NORMAL_TEXT));
+            });
+        });
+        self.custom_ammo_content(ui, settings);
+
+        let pitch: f64 = settings.angle_unit.to_radians(optional_f64(&self.table_pitch, f64::NAN));
+        let u: f64 = self.ammo_type.drag;
+        let g: f64 = self.ammo_type.gravity * settings.gravity_multiplier;
+        if !(u.is_finite() && g.is_finite() && u >= 0.0 && g > 0.0) {
+            ui.label(RichText::new(Text::InvalidAmmo.get(settings.language)).size(NORMAL_TEXT * (4.0/3.0)));
+            return;
         }
-    }
-}
+        if !pitch.is_finite() {
+            ui.label(RichText::new(Text::EnterValidPitch.get(settings.language)).size(NORMAL_TEXT * (4.0/3.0)));
+            return;
+        }
+        let rows: u32 = self.charges.parse::<u32>().unwrap_or(0).min(MAX_TABLE_ROWS);
 
-struct MyApp {
-    dock_state: DockState<MyTab>,
-    counter: usize,
-}
+        Grid::new("range-table")
+        .striped(true)
+        .min_col_width(80.0)
+        .show(ui, |ui| {
+            for header in [Text::Charges, Text::Velocity, Text::FlatRange] {
+                ui.label(RichText::new(header.get(settings.language)).size(NORMAL_TEXT));
+            }
+            ui.end_row();
 
-impl Default for MyApp {
-    fn default() -> Self {
-        let tree = DockState::new(vec![
-            MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1)),
-        ]);
+            for charges in 1..=rows {
+                let v: f64 = self.ammo_type.muzzle_velocity(charges, self.barrel_blocks());
+                ui.label(RichText::new(charges.to_string()).size(NORMAL_TEXT));
+                ui.label(RichText::new(format!("{:.1} {}/s", v, Text::Blocks.get(settings.language))).size(NORMAL_TEXT));
+                //Charges past the cap add no velocity, so these rows repeat the capped range
+                let range = RichText::new(format!("{:.1} {}", flat_range(u, v, g, pitch), Text::Blocks.get(settings.language))).size(NORMAL_TEXT);
+                ui.label(if charges > self.ammo_type.effective_charges(charges, self.barrel_blocks()) { range.weak() } else { range });
+                ui.end_row();
+            }
+        });
+    }
 
-        Self {
-            dock_state: tree,
-            counter: 2,
+    //Shown after double-clicking the tab title or picking Rename, an empty name goes back to the generated title
+    fn rename_content(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        if !self.renaming {
+            return;
         }
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("{}: ", Text::TabName.get(settings.language))).size(NORMAL_TEXT));
+            let field = ui.text_edit_singleline(&mut self.name);
+            if field.lost_focus() {
+                self.renaming = false;
+            } else if !field.has_focus() {
+                field.request_focus();
+            }
+        });
     }
-}
 
-impl eframe::App for MyApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        let mut added_nodes = Vec::new();
-        DockArea::new(&mut self.dock_state)
-            .show_add_buttons(true)
-            .show_add_popup(true)
-            .style(egui_dock::Style::from_egui(ctx.style().as_ref()))
-            .show(
-                ctx,
-                &mut TabViewer {
-                    added_nodes: &mut added_nodes,
-                },
-            );
-        
-        added_nodes.drain(..).for_each(|node| {
-            self.dock_state
-                .set_focused_node_and_surface((node.surface, node.node));
-            self.dock_state.push_to_focused_leaf(MyTab {
-                kind: node.kind,
-                surface: node.surface,
-                node: NodeIndex(self.counter),
-                c_x: node.c_x,
-                c_y: node.c_y,
-                c_z: node.c_z,
-                t_x: node.t_x,
-                t_y: node.t_y,
-                t_z: node.t_z,
-                ammo_type: node.ammo_type,
-                charges: node.charges,
-                yaw: node.yaw,
-                pitch: node.pitch,
-                time: node.time,
-                impact_angle: node.impact_angle,
-                nozzle_velocity: node.nozzle_velocity, //Remove after calibration
-                drag: node.drag //Remove after calibration
+    //Bookmarked cannon positions, shared by every tab
+    fn cannons_menu(&mut self, ui: &mut egui::Ui, settings: &mut Settings) {
+        ui.menu_button(Text::Cannons.get(settings.language), |ui| {
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.cannon_name).hint_text(Text::Name.get(settings.language)).desired_width(80.0));
+                if ui.button(Text::SaveCurrentAs.get(settings.language)).clicked() {
+                    settings.save_cannon(&self.cannon_name, [self.c_x.clone(), self.c_y.clone(), self.c_z.clone()]);
+                    self.cannon_name.clear();
+                    ui.close_menu();
+                }
             });
-            self.counter += 1;
+            if !settings.cannons.is_empty() {
+                ui.separator();
+            }
+            let mut removed: Option<usize> = None;
+            for (i, cannon) in settings.cannons.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    let position = format!("{} ({}, {}, {})", cannon.name, cannon.position[0], cannon.position[1], cannon.position[2]);
+                    if ui.button(position).clicked() {
+                        [self.c_x, self.c_y, self.c_z] = cannon.position.clone();
+                        ui.close_menu();
+                    }
+                    if ui.small_button("✖").on_hover_text(Text::ForgetCannon.get(settings.language)).clicked() {
+                        removed = Some(i);
+                    }
+                });
+            }
+            if let Some(i) = removed {
+                settings.cannons.remove(i);
+            }
         });
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    //Calculate and Clear buttons with the live toggle, inputs is the input state from before this frame's edits
+    fn calculate_content(&mut self, ui: &mut egui::Ui, settings: &Settings, submitted: bool, inputs: Vec<String>, focus_chain: &mut Vec<egui::Id>) {
+        let mut calculate = false;
+        let mut cleared = false;
+        ui.horizontal(|ui| {
+            let button = ui.button(RichText::new(Text::Calculate.get(settings.language)).size(TITLE_TEXT));
+            focus_chain.push(button.id);
+            calculate = button.clicked();
+            if ui.button(RichText::new(Text::Clear.get(settings.language)).size(TITLE_TEXT)).clicked() {
+                self.clear();
+                cleared = true;
+            }
+            ui.checkbox(&mut self.live, RichText::new(Text::Live.get(settings.language)).size(NORMAL_TEXT));
+        });
+
+        if calculate || submitted {
+            self.calculate(settings);
+        }
+
+        let changed: bool = !cleared && inputs != self.input_state();
+        if let Some(wait) = self.live_update(changed, Instant::now(), settings) {
+            ui.ctx().request_repaint_after(wait);
+        }
+    }
+
+    //Everything calculate reads from the tab, compared between frames to notice edits in live mode
+    fn input_state(&self) -> Vec<String> {
+        let mut state: Vec<String> = [
+            &self.c_x, &self.c_y, &self.c_z, &self.t_x, &self.t_y, &self.t_z, &self.v_x, &self.v_y, &self.v_z,
+            &self.distance, &self.height, &self.azimuth, &self.ammo_type.name, &self.custom_name, &self.custom_drag,
+            &self.custom_gravity, &self.charges, &self.burst_height, &self.min_elevation, &self.max_elevation,
+            &self.barrel_length, &self.barrel_blocks, &self.forward, &self.right, &self.wanted_impact,
+            &self.step_size
+        ].into_iter().cloned().collect();
+        let ammo: &Ammo = &self.ammo_type;
+        state.push(format!(
+            "{:?} {} {} {} {} {} {} {}", ammo.kind, ammo.drag, ammo.velocity_per_charge, ammo.gravity, ammo.max_charges, ammo.despawn_ticks,
+            self.relative, self.facing
+        ));
+        state.extend(self.mission_targets.iter().flatten().cloned());
+        state
+    }
+
+    //The inputs plus the settings calculate reads, snapshotted by each calculation
+    fn solve_state(&self, settings: &Settings) -> Vec<String> {
+        let mut state: Vec<String> = self.input_state();
+        state.push(format!(
+            "{:?} {:?} {:?} {:?} {} {} {}", self.preference, settings.solver, settings.angle_unit, settings.language,
+            settings.gravity_multiplier, settings.world_min_y, settings.build_height
+        ));
+        state.extend(settings.cannons.iter().map(|cannon| format!("{:?}", cannon)));
+        state
+    }
+
+    //Whether anything calculate reads changed since it last ran, so the results on screen may be stale
+    fn is_dirty(&self, settings: &Settings) -> bool {
+        self.solved_state.as_ref() != Some(&self.solve_state(settings))
+    }
+
+    //Every text input of the tab
+    fn input_fields(&mut self) -> [&mut String; 30] {
+        [
+            &mut self.c_x, &mut self.c_y, &mut self.c_z, &mut self.t_x, &mut self.t_y, &mut self.t_z, &mut self.v_x, &mut self.v_y, &mut self.v_z,
+            &mut self.distance, &mut self.height, &mut self.azimuth, &mut self.custom_name, &mut self.custom_drag, &mut self.custom_gravity,
+            &mut self.charges, &mut self.burst_height, &mut self.table_pitch, &mut self.min_elevation, &mut self.max_elevation, &mut self.barrel_length,
+            &mut self.barrel_blocks, &mut self.obstacle_distance, &mut self.obstacle_height, &mut self.step_size, &mut self.forward, &mut self.right,
+            &mut self.current_yaw, &mut self.current_pitch, &mut self.wanted_impact
+        ]
+    }
+
+    fn inputs(&mut self) -> TabInputs {
+        TabInputs {
+            fields: self.input_fields().map(|field| field.clone()).to_vec(),
+            ammo: self.ammo_type.clone(),
+            relative: self.relative,
+            facing: self.facing,
+            preference: self.preference,
+            calibration_shots: self.calibration_shots.clone(),
+            terrain: self.terrain.clone(),
+            friendlies: self.friendlies.clone(),
+            mission_targets: self.mission_targets.clone()
+        }
+    }
+
+    //Records the inputs as an undo step if they changed since the last one
+    fn commit_edit(&mut self) {
+        let state: TabInputs = self.inputs();
+        self.history.commit(state);
+    }
+
+    fn restore(&mut self, state: TabInputs) {
+        for (field, value) in self.input_fields().into_iter().zip(state.fields) {
+            *field = value;
+        }
+        self.ammo_type = state.ammo;
+        self.relative = state.relative;
+        self.facing = state.facing;
+        self.preference = state.preference;
+        self.calibration_shots = state.calibration_shots;
+        self.terrain = state.terrain;
+        self.friendlies = state.friendlies;
+        self.mission_targets = state.mission_targets;
+    }
+
+    fn undo(&mut self) {
+        if let Some(state) = self.history.undo() {
+            self.restore(state);
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(state) = self.history.redo() {
+            self.restore(state);
+        }
+    }
+
+    //Live mode recalculates once no input changed for LIVE_DELAY instead of on every keystroke
+    //A half-typed value like a lone "-" only reaches check_input, never the root-finders
+    //Returns how long is left to wait when a recalculation is pending
+    fn live_update(&mut self, changed: bool, now: Instant, settings: &Settings) -> Option<Duration> {
+        if !self.live {
+            self.edited = None;
+            return None;
+        }
+        if changed {
+            self.edited = Some(now);
+        }
+        let idle: Duration = now.duration_since(self.edited?);
+        if idle >= LIVE_DELAY {
+            self.edited = None;
+            //A lone sign or point is most likely a number still being typed, the last result stays until it has digits
+            if !self.input_state().iter().any(|value| is_partial_number(value)) {
+                self.calculate(settings);
+            }
+            None
+        } else {
+            Some(LIVE_DELAY - idle)
+        }
+    }
+
+    //Solve for the current inputs, shared by the Calculate button and Enter in any field
+    //Does nothing when the inputs are the ones the results on screen came from
+    fn calculate(&mut self, settings: &Settings) {
+        if !self.is_dirty(settings) {
+            return;
+        }
+        self.solved_state = Some(self.solve_state(settings));
+        self.max_range = self.find_max_range(settings);
+        let problems: Vec<String> = self.validate(settings);
+        if !problems.is_empty() {
+            self.reset_results();
+            self.yaw = f64::NAN;
+            self.log(format!("Not solved, {}", problems.join(", ")), true);
+            self.problems = problems;
+            return;
+        }
+        self.problems.clear();
+
+        match self.kind {
+            MyTabKind::Cartesian => {
+                //Convert input coords of cannon and target to f64 and store the difference, empty coordinates count as 0
+                let cannon: [f64; 3] = self.cannon();
+                let x: f64 = optional_f64(&self.t_x, 0.0) - cannon[0];
+                let y: f64 = optional_f64(&self.t_y, 0.0) - cannon[1];
+                let z: f64 = optional_f64(&self.t_z, 0.0) - cannon[2];
 
-    //pre-calculated data set
-    //x, y, u, v, g, a, t
-    const TESTING_DATA: [[f64; 7]; 8] = [
-        [   23.541096135,    0.959446698, 0.01,  30.0, 10.0,  0.174532925, 0.8 ],
-        [  187.001956030,   63.079770828, 0.01, 200.0, 10.0,  0.349065850, 1.0 ],
-        [   64.467192584,   26.026190686, 0.01,  50.0, 10.0,  0.523598776, 1.5 ],
-        [ 1132.001739726,  905.308887445, 0.01, 500.0, 10.0,  0.698131701, 3.0 ],
-        [ 1709.752036132, 1993.049776655, 0.01, 900.0, 10.0,  0.872664626, 3.0 ],
-        [   54.698606123,   88.712887372, 0.01, 100.0, 10.0,  1.047197551, 1.1 ],
-        [  249.003450881,  -58.274490171, 0.01, 150.0, 10.0, -0.174532925, 1.7 ],
-        [   28.120418992,  -11.482914756, 0.01,  60.0, 10.0, -0.349065850, 0.5 ],
-    ];
-
-    #[test]
-    fn angle_calculation() {
-        for i in TESTING_DATA {
-            let crit = find_critical_point(i[0], i[2], i[3], i[4]);
-            let angles = find_angles(i[0], i[1], i[2], i[3], i[4], crit);
-
-            match angles {
-                Ok(angle) => {
-                    if ! ( (0.00001 > (angle.1 - i[5]).abs()) || (0.00001 > (angle.0 - i[5]).abs())) {
-                        panic!("Failiure on test conditions {} {} {} {} {} {} {}, got crit {} and angles {} {}", i[0], i[1], i[2], i[3], i[4], i[5], i[6], crit, angle.0, angle.1)
+                //Straight above or below any yaw works, calc_yaw(0, 0) settles on 0 (south)
+                self.yaw = calc_yaw(x, z);
+
+                let d: f64 = (x*x + z*z).sqrt();
+                self.solve(d, y, settings);
+
+                //Empty velocity components count as 0, a stationary target needs no lead
+                let velocity: [f64; 3] = [optional_f64(&self.v_x, 0.0), optional_f64(&self.v_y, 0.0), optional_f64(&self.v_z, 0.0)];
+                if let Some(input) = self.solved {
+                    if velocity.iter().all(|n| n.is_finite()) && velocity.iter().any(|n| *n != 0.0) {
+                        if self.pitch.direct_shot.is_finite() {
+                            self.lead.direct_shot = Some(self.lead([x, y, z], velocity, input, false));
+                        }
+                        if self.pitch.indirect_shot.is_finite() {
+                            self.lead.indirect_shot = Some(self.lead([x, y, z], velocity, input, true));
+                        }
                     }
                 }
-                _ => {panic!("Unexpected outcome, find_angles didn't return anything")} //May change
             }
+            //Facing mode turns the offsets ahead of and to the right of the facing yaw in the azimuth field into world X/Z
+            MyTabKind::Polar if self.facing => {
+                let facing: f64 = settings.angle_unit.heading(optional_f64(&self.azimuth, 0.0));
+                let (x, z) = facing_offset(facing, optional_f64(&self.forward, 0.0), optional_f64(&self.right, 0.0));
+                let y: f64 = optional_f64(&self.height, 0.0);
+
+                self.yaw = calc_yaw(x, z);
+                self.solve((x*x + z*z).sqrt(), y, settings);
+            }
+            MyTabKind::Polar => {
+                //Distance is required, an empty height or azimuth counts as 0
+                let d: f64 = optional_f64(&self.distance, f64::NAN);
+                let y: f64 = optional_f64(&self.height, 0.0);
+
+                self.yaw = settings.angle_unit.heading(optional_f64(&self.azimuth, 0.0));
+                self.solve(d, y, settings);
+            }
+            //The range table and the landing point are cheap enough to be recomputed every frame
+            MyTabKind::RangeTable | MyTabKind::Forward => {}
+            MyTabKind::Salvo => {
+                self.reset_results();
+                let target: [f64; 3] = [optional_f64(&self.t_x, 0.0), optional_f64(&self.t_y, 0.0) + self.burst_height(), optional_f64(&self.t_z, 0.0)];
+                let g: f64 = self.ammo_type.gravity * settings.gravity_multiplier;
+                let min_elevation: f64 = optional_f64(&self.min_elevation, DEFAULT_MIN_ELEVATION);
+                let max_elevation: f64 = optional_f64(&self.max_elevation, DEFAULT_MAX_ELEVATION);
+
+                let options: Vec<Vec<SalvoShot>> = settings.cannons.iter()
+                    .map(|cannon| {
+                        let position = cannon.position.clone().map(|c| optional_f64(&c, 0.0));
+                        let offset: [f64; 3] = [target[0] - position[0], target[1] - position[1], target[2] - position[2]];
+                        salvo_options(offset, &self.ammo_type, g, min_elevation, max_elevation)
+                    })
+                    .collect();
+                let plan = plan_salvo(&options).unwrap_or_else(|| vec![None; options.len()]);
+                self.salvo = settings.cannons.iter().map(|cannon| cannon.name.clone()).zip(plan).collect();
+                let firing: usize = self.salvo.iter().filter(|(_, shot)| shot.is_some()).count();
+                self.log(format!("Salvo at ({:.1}, {:.1}, {:.1}): {} of {} cannons can fire", target[0], target[1], target[2], firing, self.salvo.len()), firing < self.salvo.len());
+            }
+            //Each row is solved as its own Cartesian tab, a row that can't be solved only fails itself
+            MyTabKind::FireMission => {
+                self.reset_results();
+                self.mission = self.mission_targets.iter()
+                    .map(|target| {
+                        if target.iter().all(|c| c.trim().is_empty()) {
+                            return Err(Text::EnterTargetCoords.get(settings.language).to_string());
+                        }
+                        let mut row = MyTab {
+                            kind: MyTabKind::Cartesian,
+                            t_x: target[0].clone(),
+                            t_y: target[1].clone(),
+                            t_z: target[2].clone(),
+                            ..self.duplicate(self.surface, self.node)
+                        };
+                        row.calculate(settings);
+                        row.mission_shot(settings)
+                    })
+                    .collect();
+                let solved: usize = self.mission.iter().filter(|shot| shot.is_ok()).count();
+                self.log(format!("Fire mission: {} of {} targets solved", solved, self.mission.len()), solved < self.mission.len());
+            }
+        }
+    }
+
+    //The solved row of a fire mission, the preferred arc unless only the other one can hit
+    fn mission_shot(&self, settings: &Settings) -> Result<SalvoShot, String> {
+        if !self.problems.is_empty() {
+            return Err(self.problems.join(", "));
+        }
+        let indirect: bool = self.preferred_arc();
+        let pitch: f64 = self.pitch.arc(indirect);
+        if !pitch.is_finite() {
+            let error: &str = error_text(self.solve_error.unwrap_or(SolveError::OutOfRange)).get(settings.language);
+            return Err(match self.min_charges {
+                Some(charges) => format!("{}, needs {} charges", error, charges),
+                None => error.to_string()
+            });
+        }
+        Ok(SalvoShot { charges: self.charges.parse().unwrap_or(0), yaw: self.yaw, pitch, time: self.time.arc(indirect) })
+    }
+
+    //Every problem with the inputs at once, so a half-filled form gets a list of what to fix instead of a confusing result
+    //Empty coordinates still count as 0, only the polar distance is required
+    fn validate(&self, settings: &Settings) -> Vec<String> {
+        let language: Language = settings.language;
+        let text = |text: Text| text.get(language).to_string();
+        let mut problems: Vec<String> = Vec::new();
+        let mut numbers: Vec<(String, &String)> = Vec::new();
+        match self.kind {
+            MyTabKind::Cartesian => {
+                if !self.relative {
+                    numbers.extend(axis_labels(Text::Cannon, [&self.c_x, &self.c_y, &self.c_z], language));
+                }
+                numbers.extend(axis_labels(Text::Target, [&self.t_x, &self.t_y, &self.t_z], language));
+                numbers.extend(axis_labels(Text::TargetVelocity, [&self.v_x, &self.v_y, &self.v_z], language));
+            }
+            MyTabKind::Polar if self.facing => numbers.extend([
+                (format!("{} Y", text(Text::Cannon)), &self.c_y), (text(Text::FacingYaw), &self.azimuth), (text(Text::Forward), &self.forward),
+                (text(Text::Right), &self.right), (text(Text::Up), &self.height)
+            ]),
+            MyTabKind::Polar => {
+                if self.distance.trim().is_empty() {
+                    problems.push(text(Text::EnterDistance));
+                }
+                numbers.extend([
+                    (format!("{} Y", text(Text::Cannon)), &self.c_y), (text(Text::Distance), &self.distance), (text(Text::Height), &self.height),
+                    (text(Text::Azimuth), &self.azimuth)
+                ]);
+            }
+            MyTabKind::Salvo => numbers.extend(axis_labels(Text::Target, [&self.t_x, &self.t_y, &self.t_z], language)),
+            //Targets are checked row by row when solving, so one bad row doesn't hold up the rest
+            MyTabKind::FireMission => numbers.extend(axis_labels(Text::Cannon, [&self.c_x, &self.c_y, &self.c_z], language)),
+            MyTabKind::RangeTable | MyTabKind::Forward => return problems,
+        }
+        numbers.push((text(Text::BarrelLength), &self.barrel_length));
+        for (label, value) in numbers {
+            if is_partial_number(value) {
+                problems.push(format!("{}: {}", label, text(Text::EnterNumber)));
+            } else if !value.trim().is_empty() && optional_f64(value, 0.0).is_nan() {
+                problems.push(format!("{} {}", label, text(Text::NotANumber)));
+            }
+        }
+
+        //The salvo fires with whatever charges each cannon needs
+        if self.kind != MyTabKind::Salvo && self.charges.parse::<u32>().unwrap_or(0) == 0 {
+            problems.push(Text::InvalidCharges.get(settings.language).to_string());
+        }
+        //Offsets in relative mode can be anything
+        let heights: Vec<(Text, &String)> = match self.kind {
+            MyTabKind::Cartesian if !self.relative => vec![(Text::Cannon, &self.c_y), (Text::Target, &self.t_y)],
+            MyTabKind::Polar => vec![(Text::Cannon, &self.c_y)],
+            MyTabKind::Salvo => vec![(Text::Target, &self.t_y)],
+            MyTabKind::FireMission => vec![(Text::Cannon, &self.c_y)],
+            _ => vec![]
+        };
+        for (label, y) in heights {
+            if let Some(hint) = settings.world_height_hint(y) {
+                problems.push(format!("{}: {}", text(label), hint));
+            }
+        }
+        if optional_f64(&self.barrel_length, 0.0) < 0.0 {
+            problems.push(text(Text::NegativeBarrel));
+        }
+        problems
+    }
+
+    //Shown instead of the results while the inputs have problems, returns whether there were any
+    fn problems_content(&self, ui: &mut egui::Ui) -> bool {
+        if self.problems.is_empty() {
+            return false;
+        }
+        egui::Frame::group(ui.style())
+        .stroke(egui::Stroke::new(1.0, ui.visuals().warn_fg_color))
+        .show(ui, |ui| {
+            for problem in &self.problems {
+                ui.label(RichText::new(problem).size(NORMAL_TEXT).color(ui.visuals().warn_fg_color));
+            }
+        });
+        true
+    }
+
+    //Solve for a target d blocks away horizontally and y blocks above the cannon, storing the results on the tab
+    fn solve(&mut self, d: f64, target_y: f64, settings: &Settings) {
+        //Smoke shells are aimed at their burst point above the target
+        let y: f64 = target_y + self.burst_height();
+
+        //The field filter still lets it be empty, that and 0 are reported as such rather than solved with no velocity
+        let charges: u32 = self.charges.parse::<u32>().unwrap_or(0);
+        if charges == 0 {
+            self.reset_results();
+            self.solve_error = Some(SolveError::InvalidInput(InputError::Charges));
+            self.target_distance = d;
+            self.target_height = target_y;
+            self.log(format!("d={:.2} y={:.2}: {}", d, y, SolveError::InvalidInput(InputError::Charges)), true);
+            return;
+        }
+        let v: f64 = self.ammo_type.muzzle_velocity(charges, self.barrel_blocks());
+        let u: f64 = self.ammo_type.drag;
+        let g: f64 = self.ammo_type.gravity * settings.gravity_multiplier;
+
+        let barrel: f64 = optional_f64(&self.barrel_length, 0.0);
+
+        let valid: bool = check_input(d, y, u, v, g).is_ok();
+        match muzzle_solution(d, y, u, v, g, barrel, settings.solver) {
+            Ok((solution, shift)) => {
+                self.solve_error = None;
+                let input = SolveInput { distance: d, height: y, drag: u, velocity: v, gravity: g, barrel };
+                self.solved = Some(input);
+                self.pitch = solution.pitch;
+                self.time = solution.time;
+                self.impact_angle = solution.impact_angle;
+                self.impact_speed = solution.impact_speed;
+                self.barrel_shift = shift;
+                self.solve_info = Some(solution.info);
+                self.snapped_pitch = Pair {
+                    direct_shot: self.snap(solution.pitch.direct_shot).unwrap_or(f64::NAN),
+                    indirect_shot: self.snap(solution.pitch.indirect_shot).unwrap_or(f64::NAN)
+                };
+                let sensitive = |pitch: f64| is_sensitive(input.at_muzzle(pitch).distance, u, v, g, pitch);
+                self.sensitive.direct_shot = sensitive(solution.pitch.direct_shot);
+                self.sensitive.indirect_shot = sensitive(solution.pitch.indirect_shot);
+                //The analytic pitches are shown next to the simulated ones for comparison
+                self.analytic_pitch = match settings.solver {
+                    Solver::Simulation => muzzle_solution(d, y, u, v, g, barrel, Solver::Analytic)
+                        .map(|(analytic, _)| analytic.pitch)
+                        .unwrap_or(Pair { direct_shot: f64::NAN, indirect_shot: f64::NAN }),
+                    Solver::Analytic => Pair { direct_shot: f64::NAN, indirect_shot: f64::NAN }
+                };
+            }
+            Err(error) => {
+                self.reset_results();
+                self.solve_error = Some(error);
+            }
+        }
+
+        //Searched even when the chosen charges fall short, that's when it's most useful
+        self.min_charges = if valid { self.ammo_type.min_charges(d, y, g, self.barrel_blocks()) } else { None };
+        //Typed as degrees below the horizon, the solver counts a descending shell's angle as negative
+        self.impact_choice = match self.wanted_impact.parse::<f64>() {
+            Ok(wanted) if valid => self.ammo_type.charges_for_impact_angle(d, y, g, self.barrel_blocks(), -wanted.abs().to_radians()),
+            _ => None
+        };
+        self.target_distance = d;
+        self.target_height = target_y;
+
+        //Angles are logged in degrees like the copied text, whatever unit the results are shown in
+        let inputs: String = format!("d={:.2} y={:.2} v={:.1} u={} g={}", d, y, v, u, g);
+        match (self.solve_error, self.solve_info) {
+            (Some(error), _) => self.log(format!("{}: {}", inputs, error), true),
+            (None, Some(info)) => {
+                let message: String = format!(
+                    "{}: direct {:.4}°, indirect {:.4}° ({}, {} iterations)",
+                    inputs, self.pitch.direct_shot.to_degrees(), self.pitch.indirect_shot.to_degrees(), info.method, info.iterations
+                );
+                self.log(message, false);
+            }
+            (None, None) => {}
         }
     }
 
-}
\ No newline at end of file
+    //Adds a timestamped entry to the tab's log, dropping the oldest past LOG_LIMIT
+    fn log(&mut self, message: String, error: bool) {
+        let seconds: u64 = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or(0);
+        self.log.push(LogEntry { time: clock_time(seconds), message, error });
+        if self.log.len() > LOG_LIMIT {
+            self.log.remove(0);
+        }
+    }
+
+    //Collapsed by default, the entries can be copied as plain text for bug reports
+    fn log_content(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        egui::CollapsingHeader::new(format!("{} ({})", Text::Log.get(settings.language), self.log.len()))
+        .id_salt("log")
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                if ui.add_enabled(!self.log.is_empty(), egui::Button::new(Text::CopyLog.get(settings.language))).clicked() {
+                    let text: Vec<String> = self.log.iter().map(|entry| format!("[{}] {}", entry.time, entry.message)).collect();
+                    ui.ctx().copy_text(text.join("\n"));
+                }
+                if ui.add_enabled(!self.log.is_empty(), egui::Button::new(Text::ClearLog.get(settings.language))).clicked() {
+                    self.log.clear();
+                }
+            });
+            egui::ScrollArea::vertical()
+            .max_height(150.0)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for entry in &self.log {
+                    let text = RichText::new(format!("[{}] {}", entry.time, entry.message)).monospace();
+                    ui.label(if entry.error { text.color(ui.visuals().error_fg_color) } else { text });
+                }
+            });
+        });
+    }
+
+    //Flat ground reach of the selected ammo and charges, stored by calculate so the readout doesn't search every frame
+    fn find_max_range(&self, settings: &Settings) -> Option<f64> {
+        let charges: u32 = self.charges.parse().ok()?;
+        let v: f64 = self.ammo_type.muzzle_velocity(charges, self.barrel_blocks());
+        let range: f64 = max_range(self.ammo_type.drag, v, self.ammo_type.gravity * settings.gravity_multiplier);
+        range.is_finite().then_some(range)
+    }
+
+    //Barrel blocks of the cannon, None while the field is empty and the barrel sets no limit
+    fn barrel_blocks(&self) -> Option<u32> {
+        self.barrel_blocks.parse().ok()
+    }
+
+    //Warning for charges past what the barrel can burn, they're fired but add no velocity
+    fn barrel_capacity_text(&self) -> Option<String> {
+        let blocks: u32 = self.barrel_blocks()?;
+        let charges: u32 = self.charges.parse().ok()?;
+        (charges > barrel_capacity(blocks))
+            .then(|| format!("A {} block barrel burns only {} charges, the rest add no velocity", blocks, barrel_capacity(blocks)))
+    }
+
+    //Height above the target a smoke shell should burst at, 0 for everything else
+    fn burst_height(&self) -> f64 {
+        if self.ammo_type.kind == AmmoType::SmokeShell {
+            optional_f64(&self.burst_height, 0.0)
+        } else {
+            0.0
+        }
+    }
+
+    fn reset_results(&mut self) {
+        self.solve_error = None;
+        self.solve_info = None;
+        self.solved = None;
+        self.pitch.direct_shot = f64::NAN;
+        self.pitch.indirect_shot = f64::NAN;
+        self.time.direct_shot = f64::NAN;
+        self.time.indirect_shot = f64::NAN;
+        self.impact_angle.direct_shot = f64::NAN;
+        self.impact_angle.indirect_shot = f64::NAN;
+        self.impact_speed.direct_shot = f64::NAN;
+        self.impact_speed.indirect_shot = f64::NAN;
+        self.barrel_shift.direct_shot = f64::NAN;
+        self.barrel_shift.indirect_shot = f64::NAN;
+        self.analytic_pitch.direct_shot = f64::NAN;
+        self.analytic_pitch.indirect_shot = f64::NAN;
+        self.snapped_pitch.direct_shot = f64::NAN;
+        self.snapped_pitch.indirect_shot = f64::NAN;
+        self.sensitive.direct_shot = false;
+        self.sensitive.indirect_shot = false;
+        self.min_charges = None;
+        self.impact_choice = None;
+        self.target_distance = f64::NAN;
+        self.target_height = f64::NAN;
+        self.lead.direct_shot = None;
+        self.lead.indirect_shot = None;
+        self.salvo.clear();
+        self.mission.clear();
+        self.problems.clear();
+    }
+
+    //Reset the target inputs and results, keeping the selected ammo and the mount's elevation limits and barrel
+    fn clear(&mut self) {
+        for field in [&mut self.c_x, &mut self.c_y, &mut self.c_z, &mut self.t_x, &mut self.t_y, &mut self.t_z, &mut self.v_x, &mut self.v_y, &mut self.v_z, &mut self.distance, &mut self.height, &mut self.azimuth, &mut self.forward, &mut self.right, &mut self.obstacle_distance, &mut self.obstacle_height] {
+            field.clear();
+        }
+        self.terrain.clear();
+        self.friendlies.clear();
+        if self.kind == MyTabKind::FireMission {
+            self.mission_targets = vec![Default::default()];
+        }
+        self.charges = "1".to_string();
+        self.yaw = f64::NAN;
+        self.edited = None;
+        self.solved_state = None;
+        self.max_range = None;
+        self.reset_results();
+    }
+
+    //Ammo type selector and number of powder charges
+    fn ammo_content(&mut self, ui: &mut egui::Ui, settings: &Settings, focus_chain: &mut Vec<egui::Id>) -> bool {
+        let mut submitted = false;
+        ui.horizontal(|ui| {
+            self.ammo_selector(ui, settings);
+
+            ui.add_space(10.0);
+
+            Grid::new("charges")
+            .max_col_width(30.0)
+            .show(ui, |ui| {
+                let charges = if settings.steppers {
+                    stepper_field(ui, &mut self.charges, 0, 1.0, focus_chain)
+                } else {
+                    chained_field(ui, &mut self.charges, verify_positive_integer_input, settings.language, focus_chain)
+                }.on_hover_text(Text::ChargesTip.get(settings.language));
+                submitted |= enter_pressed(&charges);
+            });
+
+            ui.label(RichText::new(format!(" :{}", Text::PowderCharges.get(settings.language))).size(NORMAL_TEXT));
+
+        });
+
+        if let Some(range) = self.max_range {
+            ui.label(RichText::new(format!("{}: {:.0} {}", Text::MaxRange.get(settings.language), range, Text::Blocks.get(settings.language))).size(NORMAL_TEXT));
+        }
+        if self.ammo_type.kind != AmmoType::Custom && self.ammo_type.is_uncalibrated() {
+            ui.label(RichText::new(Text::UncalibratedAmmo.get(settings.language)).size(NORMAL_TEXT).weak());
+        }
+
+        if let Some(warning) = self.barrel_capacity_text() {
+            ui.label(RichText::new(warning).size(NORMAL_TEXT).color(ui.visuals().warn_fg_color));
+        }
+
+        if self.ammo_type.kind == AmmoType::SmokeShell {
+            ui.horizontal(|ui| {
+                Grid::new("burst height")
+                .max_col_width(40.0)
+                .show(ui, |ui| {
+                    submitted |= enter_pressed(&input_field(ui, &mut self.burst_height, verify_signed_float_input, settings.language));
+                });
+                ui.label(RichText::new(" :Burst height above target").size(NORMAL_TEXT));
+            });
+        }
+
+        submitted |= self.custom_ammo_content(ui, settings);
+        submitted
+    }
+
+    fn ammo_selector(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        ComboBox::new("Ammo type", RichText::new(format!(" :{}", Text::AmmoType.get(settings.language))).size(NORMAL_TEXT))
+        .selected_text(RichText::new(ammo_label(&self.ammo_type, settings.language)).size(NORMAL_TEXT))
+        .show_ui(ui, |ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.ammo_filter).hint_text(Text::Search.get(settings.language)).desired_width(120.0));
+            for ammo in filter_ammo(Ammo::registry(&settings.ammo_profiles), &self.ammo_filter, settings.language) {
+                let name = ammo_label(&ammo, settings.language);
+                ui.selectable_value(
+                    &mut self.ammo_type,
+                    ammo,
+                    RichText::new(name).size(NORMAL_TEXT)
+                );
+            }
+            let custom = self.custom_ammo();
+            ui.selectable_value(
+                &mut self.ammo_type,
+                custom,
+                RichText::new(Text::Custom.get(settings.language)).size(NORMAL_TEXT)
+            );
+        })
+        .response
+        .on_hover_text(Text::AmmoTip.get(settings.language));
+    }
+
+    //Name, drag and gravity fields of the custom ammo profile, only shown while it's selected
+    fn custom_ammo_content(&mut self, ui: &mut egui::Ui, settings: &Settings) -> bool {
+        let mut submitted = false;
+        if self.ammo_type.kind == AmmoType::Custom {
+            ui.horizontal(|ui| {
+                Grid::new("custom ammo")
+                .max_col_width(60.0)
+                .show(ui, |ui| {
+                    submitted |= enter_pressed(&ui.text_edit_singleline(&mut self.custom_name));
+                    ui.label(RichText::new(format!(" :{}", Text::Name.get(settings.language))).size(NORMAL_TEXT));
+                    submitted |= enter_pressed(&input_field(ui, &mut self.custom_drag, verify_signed_float_input, settings.language));
+                    ui.label(RichText::new(format!(" :{}", Text::Drag.get(settings.language))).size(NORMAL_TEXT));
+                    submitted |= enter_pressed(&input_field(ui, &mut self.custom_gravity, verify_signed_float_input, settings.language));
+                    ui.label(RichText::new(format!(" :{}", Text::Gravity.get(settings.language))).size(NORMAL_TEXT));
+                });
+            });
+            self.ammo_type = self.custom_ammo();
+        }
+        submitted
+    }
+
+    //Empty or half-typed custom values parse to NaN and are rejected by solve
+    //A calibrated velocity stays with the custom ammo while it's selected, there's no field for it
+    fn custom_ammo(&self) -> Ammo {
+        let mut ammo: Ammo = Ammo::custom(
+            &self.custom_name,
+            optional_f64(&self.custom_drag, f64::NAN),
+            optional_f64(&self.custom_gravity, f64::NAN)
+        );
+        if self.ammo_type.kind == AmmoType::Custom {
+            ammo.velocity_per_charge = self.ammo_type.velocity_per_charge;
+        }
+        ammo
+    }
+
+    //Observed shots fitted to the selected ammo's drag and velocity per charge, landing points are world coordinates
+    fn calibration_content(&mut self, ui: &mut egui::Ui, settings: &mut Settings) {
+        egui::CollapsingHeader::new(Text::CalibrateAmmo.get(settings.language))
+        .show(ui, |ui| {
+            ui.label(RichText::new(format!("{} {}", Text::CalibrationHelp.get(settings.language), ammo_label(&self.ammo_type, settings.language))).weak());
+            let mut removed: Option<usize> = None;
+            Grid::new("calibration")
+            .max_col_width(60.0)
+            .show(ui, |ui| {
+                for header in [Text::Charges.get(settings.language), Text::Pitch.get(settings.language), Text::LandedX.get(settings.language), "Y", "Z"] {
+                    ui.label(RichText::new(header).size(NORMAL_TEXT));
+                }
+                ui.end_row();
+                for (i, shot) in self.calibration_shots.iter_mut().enumerate() {
+                    input_field(ui, &mut shot[0], verify_positive_integer_input, settings.language);
+                    for field in &mut shot[1..] {
+                        input_field(ui, field, verify_signed_float_input, settings.language);
+                    }
+                    if ui.small_button("✖").on_hover_text(Text::RemoveShot.get(settings.language)).clicked() {
+                        removed = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+            if let Some(i) = removed {
+                self.calibration_shots.remove(i);
+            }
+            ui.horizontal(|ui| {
+                if ui.button(Text::AddShot.get(settings.language)).clicked() {
+                    self.calibration_shots.push(Default::default());
+                }
+                if ui.button(Text::Fit.get(settings.language)).clicked() {
+                    self.calibration = Some(self.fit_calibration(settings));
+                }
+            });
+
+            match self.calibration.clone() {
+                Some(Ok(fit)) => {
+                    ui.label(RichText::new(format!(
+                        "{}: {:.5}, {}: {:.2} {}/s", Text::Drag.get(settings.language), fit.drag, Text::VelocityPerCharge.get(settings.language),
+                        fit.velocity_per_charge, Text::Blocks.get(settings.language)
+                    )).size(NORMAL_TEXT));
+                    ui.label(RichText::new(format!("{}: {:.2} {}", Text::Residual.get(settings.language), fit.residual, Text::Blocks.get(settings.language))).size(NORMAL_TEXT));
+                    if ui.button(format!("{} {}", Text::ApplyTo.get(settings.language), ammo_label(&self.ammo_type, settings.language))).clicked() {
+                        self.apply_calibration(fit, settings);
+                    }
+                }
+                Some(Err(problem)) => {
+                    ui.label(RichText::new(problem).size(NORMAL_TEXT).color(ui.visuals().error_fg_color));
+                }
+                None => {}
+            }
+        });
+    }
+
+    //Complete rows turned into shots relative to the cannon, half-filled ones are left out
+    fn fit_calibration(&self, settings: &Settings) -> Result<Calibration, String> {
+        let cannon: [f64; 3] = self.cannon();
+        let observations: Vec<Observation> = self.calibration_shots.iter()
+            .filter_map(|[charges, pitch, x, y, z]| {
+                let charges: u32 = self.ammo_type.effective_charges(charges.parse().ok()?, self.barrel_blocks());
+                let [x, y, z]: [f64; 3] = [x.trim().parse().ok()?, y.trim().parse().ok()?, z.trim().parse().ok()?];
+                Some(Observation {
+                    charges,
+                    pitch: settings.angle_unit.to_radians(pitch.trim().parse().ok()?),
+                    distance: (x - cannon[0]).hypot(z - cannon[2]),
+                    height: y - cannon[1]
+                })
+            })
+            .filter(|shot| shot.charges > 0 && shot.distance > VERTICAL_TOLERANCE)
+            .collect();
+        if observations.len() < 2 {
+            return Err(Text::NotEnoughShots.get(settings.language).to_string());
+        }
+        let g: f64 = self.ammo_type.gravity * settings.gravity_multiplier;
+        calibrate(&observations, g, self.ammo_type.drag, self.ammo_type.velocity_per_charge)
+            .ok_or_else(|| Text::NoFit.get(settings.language).to_string())
+    }
+
+    //Writes the fit into the tab's ammo and the saved profiles, so newly opened tabs pick it up too
+    fn apply_calibration(&mut self, fit: Calibration, settings: &mut Settings) {
+        self.ammo_type.drag = fit.drag;
+        self.ammo_type.velocity_per_charge = fit.velocity_per_charge;
+        if self.ammo_type.kind == AmmoType::Custom {
+            self.custom_drag = fit.drag.to_string();
+        }
+        settings.ammo_profiles.retain(|profile| profile.name != self.ammo_type.name);
+        settings.ammo_profiles.push(self.ammo_type.clone());
+        self.log(format!("Calibrated {}: drag {:.5}, {:.2} blocks/s per charge, residual {:.2} blocks", self.ammo_type.name, fit.drag, fit.velocity_per_charge, fit.residual), false);
+    }
+
+    //Where the cannon points now, the results then also show the turn to the solution
+    fn current_aim_content(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("{}: ", Text::CurrentAim.get(settings.language))).size(NORMAL_TEXT));
+            Grid::new("current aim")
+            .max_col_width(40.0)
+            .show(ui, |ui| {
+                ui.label(RichText::new(Text::Yaw.get(settings.language)).size(NORMAL_TEXT));
+                input_field(ui, &mut self.current_yaw, verify_signed_float_input, settings.language);
+                ui.label(RichText::new(Text::Pitch.get(settings.language)).size(NORMAL_TEXT));
+                input_field(ui, &mut self.current_pitch, verify_signed_float_input, settings.language);
+                ui.label(RichText::new(settings.angle_unit.symbol().trim()).size(NORMAL_TEXT));
+            });
+        });
+    }
+
+    //Turn from the current aim to a solution's pitch and the tab's yaw, the short way round
+    //None until both current angles are entered
+    fn adjustment_text(&self, pitch: f64, settings: &Settings) -> Option<String> {
+        let unit: AngleUnit = settings.angle_unit;
+        let yaw: f64 = unit.heading(self.current_yaw.trim().parse().ok()?);
+        let current_pitch: f64 = unit.to_radians(self.current_pitch.trim().parse().ok()?);
+        if !(self.yaw.is_finite() && pitch.is_finite()) {
+            return None;
+        }
+        let (yaw_delta, pitch_delta) = (shortest_yaw_delta(yaw, self.yaw), pitch - current_pitch);
+        Some(format!(
+            "Δyaw: {} {}, Δpitch: {} {}",
+            unit.format(yaw_delta.abs(), settings.decimals), if yaw_delta < 0.0 { Text::TurnLeft } else { Text::TurnRight }.get(settings.language),
+            unit.format(pitch_delta.abs(), settings.decimals), if pitch_delta < 0.0 { Text::TurnDown } else { Text::TurnUp }.get(settings.language)
+        ))
+    }
+
+    //Impact angle to find the charges and arc for, left empty when any angle will do
+    fn impact_goal_content(&mut self, ui: &mut egui::Ui, settings: &Settings) -> bool {
+        let mut submitted = false;
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("{}: ", Text::WantedImpact.get(settings.language))).size(NORMAL_TEXT));
+            Grid::new("wanted impact")
+            .max_col_width(40.0)
+            .show(ui, |ui| {
+                submitted |= enter_pressed(&input_field(ui, &mut self.wanted_impact, verify_signed_float_input, settings.language));
+                ui.label(RichText::new(Text::BelowHorizontal.get(settings.language)).size(NORMAL_TEXT));
+            });
+        });
+        submitted
+    }
+
+    //Closest impact angle to the wanted one and the charges and arc that get it
+    fn impact_choice_text(&self, settings: &Settings) -> Option<String> {
+        let choice: ImpactChoice = self.impact_choice?;
+        let (unit, decimals, language) = (settings.angle_unit, settings.decimals, settings.language);
+        let arc: Text = if choice.indirect { Text::IndirectShot } else { Text::DirectShot };
+        Some(format!(
+            "{}: {} ({}), {}: {}, {}, {}: {}",
+            Text::ClosestImpact.get(language), unit.format(choice.impact_angle, decimals), unit.format_offset(choice.error, decimals),
+            Text::PowderCharges.get(language), choice.charges, arc.get(language), Text::Pitch.get(language), unit.format(choice.pitch, decimals)
+        ))
+    }
+
+    //Elevation limits of the mount, solutions outside them are flagged as unreachable
+    fn elevation_content(&mut self, ui: &mut egui::Ui, settings: &Settings) -> bool {
+        let mut submitted = false;
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("{}: ", Text::ElevationLimits.get(settings.language))).size(NORMAL_TEXT));
+            Grid::new("elevation")
+            .max_col_width(40.0)
+            .show(ui, |ui| {
+                submitted |= enter_pressed(&input_field(ui, &mut self.min_elevation, verify_signed_float_input, settings.language));
+                ui.label(RichText::new(format!("° {}", Text::To.get(settings.language))).size(NORMAL_TEXT));
+                submitted |= enter_pressed(&input_field(ui, &mut self.max_elevation, verify_signed_float_input, settings.language));
+                ui.label(RichText::new("°").size(NORMAL_TEXT));
+            });
+        });
+        //Distance from the mount's pivot to the muzzle, where the projectile actually spawns
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("{}: ", Text::BarrelLength.get(settings.language))).size(NORMAL_TEXT));
+            Grid::new("barrel")
+            .max_col_width(40.0)
+            .show(ui, |ui| {
+                submitted |= enter_pressed(&input_field(ui, &mut self.barrel_length, verify_signed_float_input, settings.language));
+                ui.label(RichText::new(Text::Blocks.get(settings.language)).size(NORMAL_TEXT));
+            });
+        });
+        //Caps the charges that add velocity, left empty when the barrel isn't the limit
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("{}: ", Text::BarrelBlocks.get(settings.language))).size(NORMAL_TEXT));
+            Grid::new("barrel blocks")
+            .max_col_width(40.0)
+            .show(ui, |ui| {
+                submitted |= enter_pressed(&input_field(ui, &mut self.barrel_blocks, verify_positive_integer_input, settings.language));
+            });
+        });
+        //Mounts that turn in fixed increments, left empty for ones aimed freely
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("{}: ", Text::ElevationStep.get(settings.language))).size(NORMAL_TEXT));
+            Grid::new("step")
+            .max_col_width(40.0)
+            .show(ui, |ui| {
+                submitted |= enter_pressed(&input_field(ui, &mut self.step_size, verify_signed_float_input, settings.language));
+                ui.label(RichText::new(Text::PerStep.get(settings.language)).size(NORMAL_TEXT));
+            });
+        });
+        submitted
+    }
+
+    //A wall or ridge the shot has to pass over, checked against the solved arcs every frame so it needs no recalculation
+    fn obstacle_content(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("{}: ", Text::Obstacle.get(settings.language))).size(NORMAL_TEXT));
+            Grid::new("obstacle")
+            .max_col_width(40.0)
+            .show(ui, |ui| {
+                input_field(ui, &mut self.obstacle_distance, verify_signed_float_input, settings.language);
+                ui.label(RichText::new(Text::BlocksAway.get(settings.language)).size(NORMAL_TEXT));
+                input_field(ui, &mut self.obstacle_height, verify_signed_float_input, settings.language);
+                ui.label(RichText::new(Text::AboveCannon.get(settings.language)).size(NORMAL_TEXT));
+            });
+        });
+    }
+
+    //Sampled ground between the cannon and the target, as many points as the user wants to enter
+    fn terrain_content(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        let mut removed: Option<usize> = None;
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("{}: ", Text::Terrain.get(settings.language))).size(NORMAL_TEXT));
+            if ui.small_button(Text::AddPoint.get(settings.language)).clicked() {
+                self.terrain.push(Default::default());
+            }
+        });
+        Grid::new("terrain")
+        .max_col_width(40.0)
+        .show(ui, |ui| {
+            for (i, point) in self.terrain.iter_mut().enumerate() {
+                input_field(ui, &mut point[0], verify_signed_float_input, settings.language);
+                ui.label(RichText::new(Text::BlocksAway.get(settings.language)).size(NORMAL_TEXT));
+                input_field(ui, &mut point[1], verify_signed_float_input, settings.language);
+                ui.label(RichText::new(Text::AboveCannon.get(settings.language)).size(NORMAL_TEXT));
+                if ui.small_button("✖").on_hover_text(Text::RemovePoint.get(settings.language)).clicked() {
+                    removed = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(i) = removed {
+            self.terrain.remove(i);
+        }
+    }
+
+    //Own troops near the gun-target line, the height is optional and defaults to the cannon's level
+    fn friendlies_content(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        let mut removed: Option<usize> = None;
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("{}: ", Text::Friendlies.get(settings.language))).size(NORMAL_TEXT));
+            if ui.small_button(Text::AddPosition.get(settings.language)).clicked() {
+                self.friendlies.push(Default::default());
+            }
+        });
+        Grid::new("friendlies")
+        .max_col_width(40.0)
+        .show(ui, |ui| {
+            for (i, point) in self.friendlies.iter_mut().enumerate() {
+                input_field(ui, &mut point[0], verify_signed_float_input, settings.language);
+                ui.label(RichText::new(Text::BlocksAway.get(settings.language)).size(NORMAL_TEXT));
+                input_field(ui, &mut point[1], verify_signed_float_input, settings.language);
+                ui.label(RichText::new(Text::AboveCannon.get(settings.language)).size(NORMAL_TEXT));
+                if ui.small_button("✖").on_hover_text(Text::RemovePosition.get(settings.language)).clicked() {
+                    removed = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(i) = removed {
+            self.friendlies.remove(i);
+        }
+    }
+
+    //Friendly positions with a distance, an empty height means level with the cannon
+    fn friendly_points(&self) -> Vec<[f64; 2]> {
+        self.friendlies.iter()
+            .filter_map(|[distance, height]| Some([distance.parse().ok()?, optional_f64(height, 0.0)]))
+            .collect()
+    }
+
+    //Safety of one arc over the friendlies, None until a friendly sits under a solved arc
+    fn friendly_text(&self, pitch: f64, settings: &Settings, ui: &egui::Ui) -> Option<RichText> {
+        let (distance, clearance) = friendly_pass(self.solved?, pitch, &self.friendly_points())?;
+        let blocks: &str = Text::Blocks.get(settings.language);
+        Some(if clearance >= settings.friendly_clearance {
+            RichText::new(format!("{}: {:.1} {}", Text::SafeOverFriendlies.get(settings.language), clearance, blocks)).size(NORMAL_TEXT).color(CLEAR_COLOR)
+        } else {
+            let text: String = format!(
+                "{}: {:.1} {} ({}: {} {})",
+                Text::DangerToFriendlies.get(settings.language), clearance, blocks, Text::Distance.get(settings.language), distance, blocks
+            );
+            RichText::new(text).size(NORMAL_TEXT).color(ui.visuals().error_fg_color)
+        })
+    }
+
+    //Terrain samples with both fields holding a number, half-filled rows are left out
+    fn terrain_points(&self) -> Vec<[f64; 2]> {
+        self.terrain.iter()
+            .filter_map(|[distance, height]| Some([distance.parse().ok()?, height.parse().ok()?]))
+            .collect()
+    }
+
+    //Which arc to fire over the entered terrain, None until there's terrain and a solution to check
+    fn terrain_text(&self, settings: &Settings, ui: &egui::Ui) -> Option<RichText> {
+        let terrain: Vec<[f64; 2]> = self.terrain_points();
+        if terrain.is_empty() || !self.pitch.direct_shot.is_finite() {
+            return None;
+        }
+        Some(match terrain_arc(self.solved?, self.pitch, &terrain) {
+            Some(false) => RichText::new(Text::TerrainBothClear.get(settings.language)).size(NORMAL_TEXT).color(CLEAR_COLOR),
+            Some(true) => RichText::new(Text::TerrainIndirectOnly.get(settings.language)).size(NORMAL_TEXT).color(ui.visuals().warn_fg_color),
+            None => RichText::new(Text::TerrainBlocked.get(settings.language)).size(NORMAL_TEXT).color(ui.visuals().error_fg_color)
+        })
+    }
+
+    //Horizontal distance and height of the obstacle, None until both fields hold a number
+    fn obstacle(&self) -> Option<[f64; 2]> {
+        Some([self.obstacle_distance.parse().ok()?, self.obstacle_height.parse().ok()?])
+    }
+
+    fn obstacle_text(&self, pitch: f64, settings: &Settings, ui: &egui::Ui) -> Option<RichText> {
+        let clears: bool = clears_obstacle(self.solved?, pitch, self.obstacle()?)?;
+        Some(if clears {
+            RichText::new(Text::ClearsObstacle.get(settings.language)).size(NORMAL_TEXT).color(CLEAR_COLOR)
+        } else {
+            RichText::new(Text::BlockedByObstacle.get(settings.language)).size(NORMAL_TEXT).color(ui.visuals().error_fg_color)
+        })
+    }
+
+    fn lead(&self, target: [f64; 3], velocity: [f64; 3], input: SolveInput, indirect: bool) -> Lead {
+        let (pitch, time) = if indirect {
+            (self.pitch.indirect_shot, self.time.indirect_shot)
+        } else {
+            (self.pitch.direct_shot, self.time.direct_shot)
+        };
+        //Led from where the muzzle sits for the static aim, the lead only moves it a little
+        let [forward, up] = input.muzzle(pitch);
+        let (x, z) = facing_offset(self.yaw, forward, 0.0);
+        let target: [f64; 3] = [target[0] - x, target[1] + self.burst_height() - up, target[2] - z];
+        match lead_solution(target, velocity, input.drag, input.velocity, input.gravity, indirect, time) {
+            Some((yaw, pitch)) => Lead { yaw, pitch, converged: true },
+            None => Lead { yaw: self.yaw, pitch, converged: false }
+        }
+    }
+
+    //Cannon position, the origin in relative mode where the target fields already hold the offsets
+    fn cannon(&self) -> [f64; 3] {
+        if self.relative {
+            [0.0, 0.0, 0.0]
+        } else {
+            [optional_f64(&self.c_x, 0.0), optional_f64(&self.c_y, 0.0), optional_f64(&self.c_z, 0.0)]
+        }
+    }
+
+    fn is_reachable(&self, pitch: f64) -> bool {
+        elevation_reachable(
+            pitch,
+            optional_f64(&self.min_elevation, DEFAULT_MIN_ELEVATION),
+            optional_f64(&self.max_elevation, DEFAULT_MAX_ELEVATION)
+        )
+    }
+
+    //Absolute Y of the highest point of a solved trajectory
+    fn apex(&self, pitch: f64) -> f64 {
+        match self.solved {
+            //Fired straight up the shot meets the target on its way up
+            Some(input) if input.distance < VERTICAL_TOLERANCE => self.cannon()[1] + input.height.max(0.0),
+            Some(input) => {
+                let muzzle: SolveInput = input.at_muzzle(pitch);
+                self.cannon()[1] + input.muzzle(pitch)[1] + apex_height(muzzle.distance, input.drag, input.velocity, input.gravity, pitch)
+            }
+            None => f64::NAN
+        }
+    }
+
+    //Whether the arc to fire is the indirect one, the preferred arc unless only the other one can hit
+    fn preferred_arc(&self) -> bool {
+        match self.preference {
+            Preference::Flat => !self.pitch.direct_shot.is_finite(),
+            Preference::Lobbed => self.pitch.indirect_shot.is_finite()
+        }
+    }
+
+    //Target, ammo, yaw and pitch as listed in the overview, "—" for anything not entered or calculated yet
+    fn overview_row(&self, settings: &Settings) -> [String; 4] {
+        let dash = || "—".to_string();
+        let target: String = match self.kind {
+            MyTabKind::Cartesian | MyTabKind::Salvo if [&self.t_x, &self.t_y, &self.t_z].iter().any(|c| !c.trim().is_empty()) => {
+                format!("{}, {}, {}", optional_f64(&self.t_x, 0.0), optional_f64(&self.t_y, 0.0), optional_f64(&self.t_z, 0.0))
+            }
+            MyTabKind::Polar if self.facing => format!("{} ahead, {} right", optional_f64(&self.forward, 0.0), optional_f64(&self.right, 0.0)),
+            MyTabKind::Polar if !self.distance.trim().is_empty() => format!("{} blocks away", self.distance.trim()),
+            MyTabKind::FireMission => format!("{} target{}", self.mission_targets.len(), if self.mission_targets.len() == 1 { "" } else { "s" }),
+            _ => dash()
+        };
+        let angle = |angle: f64| if angle.is_finite() { settings.angle_unit.format(angle, settings.decimals) } else { dash() };
+        [target, ammo_label(&self.ammo_type, settings.language), angle(self.yaw), angle(self.pitch.arc(self.preferred_arc()))]
+    }
+
+    fn yaw_text(&self, settings: &Settings) -> String {
+        let decimals: usize = settings.decimals;
+        let unit: AngleUnit = settings.angle_unit;
+        let yaw: &str = Text::Yaw.get(settings.language);
+        if !self.yaw.is_finite() {
+            format!("{}: {}", yaw, unit.format(self.yaw, decimals))
+        } else if settings.game_angles {
+            format!("{}: {} ({}) | {} {:.*}", yaw, unit.format(self.yaw, decimals), compass_direction(self.yaw), Text::InGame.get(settings.language), decimals, game_yaw(self.yaw))
+        } else {
+            format!("{}: {} ({})", yaw, unit.format(self.yaw, decimals), compass_direction(self.yaw))
+        }
+    }
+
+    //How much the muzzle offset moved the pitch, only shown with a barrel length set
+    fn barrel_text(shift: f64, settings: &Settings) -> Option<RichText> {
+        if !shift.is_finite() || shift == 0.0 {
+            return None;
+        }
+        let text = RichText::new(format!("{}: {}", Text::BarrelCorrection.get(settings.language), settings.angle_unit.format_offset(shift, settings.decimals))).size(NORMAL_TEXT);
+        Some(if shift.abs() < BARREL_NOTICEABLE.to_radians() { text.weak() } else { text })
+    }
+
+    //Analytic pitch for the same arc while the simulation is solving, and how far the simulated one is from it
+    fn analytic_text(pitch: f64, analytic: f64, settings: &Settings) -> Option<RichText> {
+        if !analytic.is_finite() {
+            return None;
+        }
+        let unit: AngleUnit = settings.angle_unit;
+        let text: String = format!(
+            "{}: {} ({})", Text::Analytic.get(settings.language), unit.format(analytic, settings.decimals), unit.format_offset(pitch - analytic, settings.decimals)
+        );
+        Some(RichText::new(text).size(NORMAL_TEXT).weak())
+    }
+
+    //Nearest pitch the mount can actually be set to, None for a freely aimed mount or a missing arc
+    fn snap(&self, pitch: f64) -> Option<f64> {
+        let step: f64 = self.step_size.parse().ok().filter(|step: &f64| *step > 0.0 && pitch.is_finite())?;
+        let (steps, _) = elevation_steps(pitch, step);
+        Some((steps as f64 * step).to_radians())
+    }
+
+    //Steps to count on the mount for a pitch, only shown with a positive step size set
+    fn steps_text(&self, pitch: f64, settings: &Settings) -> Option<String> {
+        let step: f64 = self.step_size.parse().ok().filter(|step: &f64| *step > 0.0)?;
+        let (steps, residual) = elevation_steps(pitch, step);
+        Some(format!("→ {} {} ({:+.*}°)", steps, Text::Steps.get(settings.language), settings.decimals, residual))
+    }
+
+    //Where the shot lands when the pitch is rounded to the nearest mount step, from the same trajectory as the plot
+    fn aim_error_text(&self, pitch: f64, settings: &Settings) -> Option<String> {
+        let achievable: f64 = self.snap(pitch)?;
+        let input = self.solved.filter(|input| input.distance >= VERTICAL_TOLERANCE)?.at_muzzle(pitch);
+        let (range, height) = aim_error(input.distance, input.drag, input.velocity, input.gravity, pitch, achievable);
+        let (label, blocks): (&str, &str) = (Text::AimError.get(settings.language), Text::Blocks.get(settings.language));
+        Some(if range.is_finite() {
+            format!("{}: {:+.1} {} {}, {:+.1} {} Y", label, range, blocks, Text::Range.get(settings.language), height, blocks)
+        } else {
+            format!("{}: {}, {:+.1} {} Y", label, Text::NeverReachesHeight.get(settings.language), height, blocks)
+        })
+    }
+
+    fn pitch_text(pitch: f64, settings: &Settings) -> String {
+        let decimals: usize = settings.decimals;
+        let label: &str = Text::Pitch.get(settings.language);
+        if settings.game_angles {
+            format!("{}: {} | {} {:.*}", label, settings.angle_unit.format(pitch, decimals), Text::InGame.get(settings.language), decimals, game_pitch(pitch))
+        } else {
+            format!("{}: {}", label, settings.angle_unit.format(pitch, decimals))
+        }
+    }
+
+    //Arc to highlight, true for the indirect one: the preferred arc, or whichever is left when the other misses or is out of the mount's reach
+    fn preferred_shot(&self) -> Option<bool> {
+        let usable = |pitch: f64| pitch.is_finite() && self.is_reachable(pitch);
+        match (usable(self.pitch.direct_shot), usable(self.pitch.indirect_shot)) {
+            (true, true) => Some(self.preference == Preference::Lobbed),
+            (true, false) => Some(false),
+            (false, true) => Some(true),
+            (false, false) => None
+        }
+    }
+
+    //Group around one arc's results, outlined in its plot color when it's the one to use and faded when it isn't
+    fn solution_group(&self, ui: &mut egui::Ui, indirect: bool, add_contents: impl FnOnce(&mut egui::Ui)) {
+        let preferred = self.preferred_shot();
+        let mut frame = egui::Frame::group(ui.style());
+        if preferred == Some(indirect) {
+            frame = frame.stroke(egui::Stroke::new(2.0, if indirect { INDIRECT_COLOR } else { DIRECT_COLOR }));
+        }
+        frame.show(ui, |ui| {
+            if preferred == Some(!indirect) {
+                ui.multiply_opacity(0.5);
+            }
+            add_contents(ui);
+        });
+    }
+
+    //One arc's results, only lobs are checked against the safe flight time
+    fn shot_content(&self, ui: &mut egui::Ui, settings: &Settings, indirect: bool, title: &str) {
+        ui.label(RichText::new(title).size(NORMAL_TEXT * (4.0/3.0))).on_hover_text(self.shot_tip(indirect).get(settings.language));
+        ui.label(RichText::new(self.yaw_text(settings)).size(NORMAL_TEXT)).on_hover_text(Text::YawTip.get(settings.language));
+        if self.pitch.arc(indirect).is_finite() {
+            ui.label(RichText::new(Self::pitch_text(self.pitch.arc(indirect), settings)).size(NORMAL_TEXT));
+            if let Some(adjustment) = self.adjustment_text(self.pitch.arc(indirect), settings) {
+                ui.label(RichText::new(adjustment).size(NORMAL_TEXT));
+            }
+            if let Some(analytic) = Self::analytic_text(self.pitch.arc(indirect), self.analytic_pitch.arc(indirect), settings) {
+                ui.label(analytic);
+            }
+            if let Some(steps) = self.steps_text(self.pitch.arc(indirect), settings) {
+                ui.label(RichText::new(steps).size(NORMAL_TEXT));
+            }
+            if self.snapped_pitch.arc(indirect).is_finite() {
+                let snapped: String = settings.angle_unit.format(self.snapped_pitch.arc(indirect), settings.decimals);
+                ui.label(RichText::new(format!("{}: {}", Text::SnappedPitch.get(settings.language), snapped)).size(NORMAL_TEXT));
+            }
+            if let Some(error) = self.aim_error_text(self.pitch.arc(indirect), settings) {
+                ui.label(RichText::new(error).size(NORMAL_TEXT));
+            }
+            if !self.is_reachable(self.pitch.arc(indirect)) {
+                ui.label(RichText::new(Text::UnreachableElevation.get(settings.language)).size(NORMAL_TEXT).color(ui.visuals().error_fg_color));
+            }
+            if let Some(correction) = Self::barrel_text(self.barrel_shift.arc(indirect), settings) {
+                ui.label(correction);
+            }
+            ui.label(RichText::new(format!("{}: {:.*}s ({} {})", Text::FlightTime.get(settings.language), settings.decimals, self.time.arc(indirect), seconds_to_ticks(self.time.arc(indirect)), Text::Ticks.get(settings.language))).size(NORMAL_TEXT));
+            if self.ammo_type.despawns(self.time.arc(indirect)) {
+                ui.label(RichText::new(Text::Despawns.get(settings.language)).size(NORMAL_TEXT).color(ui.visuals().error_fg_color));
+            }
+            if indirect && settings.exceeds_safe_flight_time(self.time.arc(indirect)) {
+                ui.label(RichText::new(Text::LongFlight.get(settings.language)).size(NORMAL_TEXT).color(ui.visuals().warn_fg_color));
+            }
+            if self.ammo_type.is_timed() {
+                let fuze: String = format!("{}: {} {}", Text::Fuze.get(settings.language), seconds_to_ticks(self.time.arc(indirect)), Text::Ticks.get(settings.language));
+                ui.label(RichText::new(fuze).size(NORMAL_TEXT));
+            }
+            ui.label(RichText::new(format!("{}: {}", Text::ImpactAngle.get(settings.language), settings.angle_unit.format(self.impact_angle.arc(indirect), settings.decimals))).size(NORMAL_TEXT));
+            ui.label(RichText::new(format!("{}: {:.1} {}/s", Text::ImpactSpeed.get(settings.language), self.impact_speed.arc(indirect), Text::Blocks.get(settings.language))).size(NORMAL_TEXT));
+            if self.sensitive.arc(indirect) {
+                ui.label(RichText::new(Text::Sensitive.get(settings.language)).color(ui.visuals().warn_fg_color));
+            }
+            if let Some(obstacle) = self.obstacle_text(self.pitch.arc(indirect), settings, ui) {
+                ui.label(obstacle);
+            }
+            if let Some(friendlies) = self.friendly_text(self.pitch.arc(indirect), settings, ui) {
+                ui.label(friendlies);
+            }
+            let apex = self.apex(self.pitch.arc(indirect));
+            ui.label(RichText::new(format!("{}: {:.1}", Text::Apex.get(settings.language), apex)).size(NORMAL_TEXT));
+            if apex > settings.build_height {
+                ui.label(RichText::new(Text::ApexAboveBuildHeight.get(settings.language)).color(ui.visuals().warn_fg_color));
+            }
+            if let Some(lead) = self.lead.arc(indirect) {
+                ui.label(RichText::new(format!("{}: {}", Text::LeadYaw.get(settings.language), settings.angle_unit.format(lead.yaw, settings.decimals))).size(NORMAL_TEXT));
+                ui.label(RichText::new(format!("{}: {}", Text::LeadPitch.get(settings.language), settings.angle_unit.format(lead.pitch, settings.decimals))).size(NORMAL_TEXT));
+                if !lead.converged {
+                    ui.label(RichText::new(Text::LeadNotConverged.get(settings.language)).color(ui.visuals().warn_fg_color));
+                }
+            }
+        } else {
+            ui.label(RichText::new(error_text(self.solve_error.unwrap_or(SolveError::OutOfRange)).get(settings.language)).size(NORMAL_TEXT * (4.0/3.0)));
+        }
+    }
+
+    //What the arc is good for, shown when hovering its title
+    fn shot_tip(&self, indirect: bool) -> Text {
+        if self.merged_shot() {
+            Text::MergedTip
+        } else if indirect {
+            Text::IndirectTip
+        } else {
+            Text::DirectTip
+        }
+    }
+
+    //Both arcs within MERGED_PITCH_EPSILON of each other are the single shot at maximum range
+    fn merged_shot(&self) -> bool {
+        self.pitch.direct_shot.is_finite() && (self.pitch.direct_shot - self.pitch.indirect_shot).abs() < MERGED_PITCH_EPSILON
+    }
+
+    //Two distinct arcs the mount's step rounds to the same setting, so only one shot can actually be fired
+    fn snapped_same(&self) -> bool {
+        !self.merged_shot() && self.snapped_pitch.direct_shot == self.snapped_pitch.indirect_shot
+    }
+
+    fn results_content(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        if self.problems_content(ui) {
+            return;
+        }
+
+        //Ground distance and height difference the solution was computed for
+        if self.target_distance.is_finite() && self.target_height.is_finite() {
+            ui.horizontal(|ui| {
+                let blocks: &str = Text::Blocks.get(settings.language);
+                ui.label(RichText::new(format!("{}: {:.2} {}", Text::Distance.get(settings.language), self.target_distance, blocks)).size(NORMAL_TEXT));
+                ui.add_space(10.0);
+                ui.label(RichText::new(format!("{}: {:.2} {}", Text::Height.get(settings.language), self.target_height, blocks)).size(NORMAL_TEXT));
+            });
+        }
+
+        if !self.merged_shot() && !self.snapped_same() {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(format!("{}: ", Text::Prefer.get(settings.language))).size(NORMAL_TEXT));
+                ui.radio_value(&mut self.preference, Preference::Flat, Text::Flat.get(settings.language));
+                ui.radio_value(&mut self.preference, Preference::Lobbed, Text::Lobbed.get(settings.language));
+            });
+        }
+
+        //At maximum range both arcs are the same shot, shown once instead of as two identical groups
+        if self.merged_shot() {
+            egui::Frame::group(ui.style())
+            .stroke(egui::Stroke::new(2.0, ui.visuals().warn_fg_color))
+            .show(ui, |ui| {
+                self.shot_content(ui, settings, false, Text::MaximumRangeShot.get(settings.language));
+            });
+        } else if self.snapped_same() {
+            egui::Frame::group(ui.style())
+            .stroke(egui::Stroke::new(2.0, ui.visuals().warn_fg_color))
+            .show(ui, |ui| {
+                ui.label(RichText::new(Text::SnappedSame.get(settings.language)).size(NORMAL_TEXT).color(ui.visuals().warn_fg_color));
+                self.shot_content(ui, settings, false, Text::DirectShot.get(settings.language));
+            });
+        } else {
+            Grid::new("results")
+            .min_col_width(ui.available_width() / 2.0)
+            .max_col_width(ui.available_width() / 2.0)
+            .show(ui, |ui| {
+                ui.vertical(|ui| {
+                    self.solution_group(ui, false, |ui| self.shot_content(ui, settings, false, Text::DirectShot.get(settings.language)));
+                });
+                ui.vertical(|ui| {
+                    self.solution_group(ui, true, |ui| self.shot_content(ui, settings, true, Text::IndirectShot.get(settings.language)));
+                });
+            });
+        }
+        if let Some(terrain) = self.terrain_text(settings, ui) {
+            ui.label(terrain);
+        }
+
+        //Only meaningful once a target with valid inputs has been calculated
+        if self.yaw.is_finite() && !matches!(self.solve_error, Some(SolveError::InvalidInput(_))) {
+            match self.min_charges {
+                Some(charges) => ui.label(RichText::new(format!("{}: {}", Text::MinCharges.get(settings.language), charges)).size(NORMAL_TEXT)),
+                None => ui.label(RichText::new(format!("{}: {}", Text::MinCharges.get(settings.language), self.ammo_type.max_charges)).size(NORMAL_TEXT).weak())
+            };
+            if let Some(choice) = self.impact_choice_text(settings) {
+                ui.label(RichText::new(choice).size(NORMAL_TEXT));
+            }
+        }
+
+        //Which path the root-finding took, for bug reports about odd solutions
+        if let Some(info) = self.solve_info {
+            egui::CollapsingHeader::new(Text::SolverDetails.get(settings.language))
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.label(format!("{}: {}", Text::Method.get(settings.language), info.method));
+                ui.label(format!("{}: {} ({} Newton)", Text::Iterations.get(settings.language), info.iterations, info.newton_iterations));
+            });
+        }
+
+        //Copy a single solution to the clipboard to relay it to teammates
+        ui.horizontal(|ui| {
+            if ui.add_enabled(self.pitch.direct_shot.is_finite(), egui::Button::new(Text::CopyDirect.get(settings.language))).clicked() {
+                ui.ctx().copy_text(solution_text("Direct shot", self.yaw, self.pitch.direct_shot, self.time.direct_shot, self.impact_angle.direct_shot, settings.decimals));
+            }
+            if ui.add_enabled(self.pitch.indirect_shot.is_finite(), egui::Button::new(Text::CopyIndirect.get(settings.language))).clicked() {
+                ui.ctx().copy_text(solution_text("Indirect shot", self.yaw, self.pitch.indirect_shot, self.time.indirect_shot, self.impact_angle.indirect_shot, settings.decimals));
+            }
+            if ui.button(Text::ExportReport.get(settings.language)).clicked() {
+                self.export_report();
+            }
+        });
+
+        //Trajectories of both solutions, hidden while there's no valid solution
+        if let Some(input) = self.solved {
+            //Only one arc may exist, the missing one has a NaN pitch
+            let direct = self.pitch.direct_shot.is_finite().then(|| input.points(self.pitch.direct_shot));
+            let indirect = self.pitch.indirect_shot.is_finite().then(|| input.points(self.pitch.indirect_shot));
+
+            //Same pitches without drag, to show how much shorter the air makes the shot
+            ui.checkbox(&mut self.vacuum_arc, RichText::new(Text::VacuumArc.get(settings.language)).size(NORMAL_TEXT));
+            let vacuum = |pitch: f64| (self.vacuum_arc && pitch.is_finite()).then(|| input.vacuum_points(pitch));
+            let (direct_vacuum, indirect_vacuum) = (vacuum(self.pitch.direct_shot), vacuum(self.pitch.indirect_shot));
+
+            //Axes scale independently, flattening long shots would hide the arc so the height ends up exaggerated
+            let text = |text: Text| text.get(settings.language);
+            Plot::new("trajectory")
+            .height(250.0)
+            .legend(Legend::default())
+            .x_axis_label(format!("{} ({})", text(Text::Distance), text(Text::Blocks)))
+            .y_axis_label(format!("{} ({})", text(Text::Height), text(Text::Blocks)))
+            .label_formatter(|name, point| {
+                let coordinates = format!("{:.1}, {:.1}", point.x, point.y);
+                if name.is_empty() { coordinates } else { format!("{}\n{}", name, coordinates) }
+            })
+            .show(ui, |plot_ui| {
+                if let Some(direct) = direct {
+                    plot_ui.line(Line::new(direct).name(text(Text::DirectShot)).color(DIRECT_COLOR));
+                }
+                if let Some(indirect) = indirect {
+                    plot_ui.line(Line::new(indirect).name(text(Text::IndirectShot)).color(INDIRECT_COLOR));
+                }
+                if let Some(direct) = direct_vacuum {
+                    plot_ui.line(Line::new(direct).name(text(Text::VacuumArc)).color(DIRECT_COLOR).style(LineStyle::dashed_loose()));
+                }
+                if let Some(indirect) = indirect_vacuum {
+                    plot_ui.line(Line::new(indirect).name(text(Text::VacuumArc)).color(INDIRECT_COLOR).style(LineStyle::dashed_loose()));
+                }
+                plot_ui.hline(HLine::new(self.target_height).name(text(Text::TargetHeight)).style(LineStyle::dashed_loose()));
+                if let Some([distance, height]) = self.obstacle() {
+                    plot_ui.line(Line::new(vec![[distance, height.min(0.0)], [distance, height]]).name(text(Text::Obstacle)).width(3.0));
+                }
+                let mut terrain: Vec<[f64; 2]> = self.terrain_points();
+                if !terrain.is_empty() {
+                    terrain.sort_by(|a, b| a[0].total_cmp(&b[0]));
+                    plot_ui.line(Line::new(terrain).name(text(Text::Terrain)).width(2.0));
+                }
+                let friendlies: Vec<[f64; 2]> = self.friendly_points();
+                if !friendlies.is_empty() {
+                    plot_ui.points(Points::new(friendlies).radius(4.0).shape(MarkerShape::Square).name(text(Text::Friendlies)));
+                }
+                plot_ui.points(Points::new(vec![[input.distance, input.height]]).radius(4.0).name(text(Text::Target)));
+            });
+        }
+    }
+
+    //Relative tabs write the cannon at the origin so the target columns read as offsets
+    fn csv_cannon(&self, field: &str) -> String {
+        if self.relative { "0".to_string() } else { field.to_string() }
+    }
+
+    //Shareable summary of the inputs and both solutions, angles in degrees like the CSV export
+    fn to_markdown(&self) -> String {
+        let number = |n: f64, unit: &str| if n.is_finite() { format!("{:.4}{}", n + 0.0, unit) } else { "—".to_string() };
+        let degrees = |a: f64| number(a.to_degrees(), "°");
+        let field = |value: &String| optional_f64(value, 0.0).to_string();
+
+        let mut inputs: Vec<(&str, String)> = Vec::new();
+        match self.kind {
+            MyTabKind::Polar if self.facing => inputs.extend([
+                ("Facing yaw", field(&self.azimuth)), ("Forward", field(&self.forward)), ("Right", field(&self.right)), ("Up", field(&self.height))
+            ]),
+            MyTabKind::Polar => inputs.extend([("Distance", field(&self.distance)), ("Height", field(&self.height)), ("Azimuth", field(&self.azimuth))]),
+            _ => {
+                if !self.relative {
+                    inputs.push(("Cannon", format!("{}, {}, {}", field(&self.c_x), field(&self.c_y), field(&self.c_z))));
+                }
+                let target: &str = if self.relative { "Target offset" } else { "Target" };
+                inputs.push((target, format!("{}, {}, {}", field(&self.t_x), field(&self.t_y), field(&self.t_z))));
+            }
+        }
+        inputs.push(("Ammo", self.ammo_type.name.clone()));
+        inputs.push(("Charges", self.charges.clone()));
+        if optional_f64(&self.barrel_length, 0.0) != 0.0 {
+            inputs.push(("Barrel length", field(&self.barrel_length)));
+        }
+        if self.ammo_type.kind == AmmoType::SmokeShell {
+            inputs.push(("Burst height", self.burst_height().to_string()));
+        }
+
+        let mut report: String = format!("# {}\n\n## Inputs\n\n| Input | Value |\n|---|---|\n", self.title(Language::English));
+        for (label, value) in inputs {
+            report += &format!("| {} | {} |\n", label, value);
+        }
+
+        report += "\n## Solutions\n\n| | Direct | Indirect |\n|---|---|---|\n";
+        let mut rows: Vec<(&str, Pair<String>)> = vec![
+            ("Yaw", Pair { direct_shot: degrees(self.yaw), indirect_shot: degrees(self.yaw) }),
+            ("Pitch", Pair { direct_shot: degrees(self.pitch.direct_shot), indirect_shot: degrees(self.pitch.indirect_shot) }),
+            ("Flight time", Pair { direct_shot: number(self.time.direct_shot, "s"), indirect_shot: number(self.time.indirect_shot, "s") }),
+            ("Impact angle", Pair { direct_shot: degrees(self.impact_angle.direct_shot), indirect_shot: degrees(self.impact_angle.indirect_shot) })
+        ];
+        if self.ammo_type.is_timed() {
+            let fuze = |t: f64| if t.is_finite() { format!("{} ticks", seconds_to_ticks(t)) } else { "—".to_string() };
+            rows.push(("Fuze", Pair { direct_shot: fuze(self.time.direct_shot), indirect_shot: fuze(self.time.indirect_shot) }));
+        }
+        for (label, row) in rows {
+            report += &format!("| {} | {} | {} |\n", label, row.direct_shot, row.indirect_shot);
+        }
+
+        report += "\n## Range\n\n";
+        report += &format!("- Distance: {}\n- Height: {}\n", number(self.target_distance, " blocks"), number(self.target_height, " blocks"));
+        if let Some(input) = self.solved {
+            report += &format!("- Max range: {}\n", number(max_range(input.drag, input.velocity, input.gravity), " blocks"));
+
+            let arcs: Vec<(Vec<[f64; 2]>, char)> = [(self.pitch.direct_shot, '*'), (self.pitch.indirect_shot, 'o')].into_iter()
+                .filter(|(pitch, _)| pitch.is_finite())
+                .map(|(pitch, mark)| (input.points(pitch), mark))
+                .chain([(vec![[input.distance, input.height]], 'X')])
+                .collect();
+            let (width, height) = ASCII_PLOT_SIZE;
+            report += &format!("\n## Trajectory\n\n`*` direct, `o` indirect, `X` target\n\n```\n{}```\n", ascii_trajectory(&arcs, width, height));
+        }
+        report
+    }
+
+    //Saves the Markdown report to a file picked by the user, the outcome goes to the tab's log
+    fn export_report(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Markdown", &["md"])
+            .set_file_name(format!("{}.md", self.title(Language::English)))
+            .save_file()
+        else {
+            return;
+        };
+
+        match std::fs::write(&path, self.to_markdown()) {
+            Ok(()) => self.log(format!("Report saved to {}", path.display()), false),
+            Err(error) => self.log(format!("Saving the report failed: {}", error), true)
+        }
+    }
+
+    fn csv_row(&self) -> String {
+        [
+            csv_field(&self.csv_cannon(&self.c_x)), csv_field(&self.csv_cannon(&self.c_y)), csv_field(&self.csv_cannon(&self.c_z)),
+            csv_field(&self.t_x), csv_field(&self.t_y), csv_field(&self.t_z),
+            csv_field(&self.ammo_type.name), csv_field(&self.charges),
+            csv_number(self.yaw, true),
+            csv_number(self.pitch.direct_shot, true), csv_number(self.pitch.indirect_shot, true),
+            csv_number(self.time.direct_shot, false), csv_number(self.time.indirect_shot, false),
+            csv_number(self.impact_angle.direct_shot, true), csv_number(self.impact_angle.indirect_shot, true)
+        ].join(",")
+    }
+
+    //A name given by the user wins over the generated title
+    fn title(&self, language: Language) -> String {
+        if !self.name.trim().is_empty() {
+            return self.name.clone();
+        }
+        match self.kind {
+            MyTabKind::Cartesian => format!("{} {}", Text::CartesianTab.get(language), self.node.0),
+            MyTabKind::Polar => format!("{} {}", Text::PolarTab.get(language), self.node.0),
+            MyTabKind::RangeTable => format!("{} {}", Text::RangeTable.get(language), self.node.0),
+            MyTabKind::Salvo => format!("{} {}", Text::Salvo.get(language), self.node.0),
+            MyTabKind::FireMission => format!("{} {}", Text::FireMission.get(language), self.node.0),
+            MyTabKind::Forward => format!("{} {}", Text::FixedPitch.get(language), self.node.0),
+        }
+    }
+}
+struct TabViewer<'a> {
+    added_nodes: &'a mut Vec<MyTab>,
+    close_tabs: &'a mut Option<CloseTabs>,
+    settings: &'a mut Settings,
+}
+
+//Tabs to close from a tab's context menu, applied after the dock is drawn
+//Others and ToTheRight hold the number of the tab they were picked on, which is never closed
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum CloseTabs {
+    Others(usize),
+    ToTheRight(usize),
+    All,
+}
+
+impl egui_dock::TabViewer for TabViewer<'_> {
+    type Tab = MyTab;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> egui_dock::egui::WidgetText {
+        tab.title(self.settings.language).into()
+    }
+
+    //Node indices are never reused, unlike titles which can be renamed or translated
+    fn id(&mut self, tab: &mut Self::Tab) -> egui::Id {
+        egui::Id::new(("tab", tab.node.0))
+    }
+
+    fn on_tab_button(&mut self, tab: &mut Self::Tab, response: &egui::Response) {
+        if response.double_clicked() {
+            tab.renaming = true;
+        }
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {
+        tab.rename_content(ui, self.settings);
+        match tab.kind {
+            MyTabKind::Cartesian => tab.cartesian_tab_content(ui, self.settings),
+            MyTabKind::Polar => tab.polar_tab_content(ui, self.settings),
+            MyTabKind::RangeTable => tab.range_table_content(ui, self.settings),
+            MyTabKind::Salvo => tab.salvo_content(ui, self.settings),
+            MyTabKind::FireMission => tab.fire_mission_content(ui, self.settings),
+            MyTabKind::Forward => tab.forward_content(ui, self.settings),
+        }
+        if !matches!(tab.kind, MyTabKind::RangeTable | MyTabKind::Forward) {
+            ui.separator();
+            tab.log_content(ui, self.settings);
+        }
+        //Edits are committed once the field loses focus, so a typed number is one undo step
+        if ui.ctx().memory(|memory| memory.focused().is_none()) {
+            tab.commit_edit();
+        }
+    }
+
+    fn context_menu(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab, surface: SurfaceIndex, node: NodeIndex) {
+        if ui.button(Text::Rename.get(self.settings.language)).clicked() {
+            tab.renaming = true;
+            ui.close_menu();
+        }
+        if ui.button(Text::Duplicate.get(self.settings.language)).clicked() {
+            self.added_nodes.push(tab.duplicate(surface, node));
+            ui.close_menu();
+        }
+        ui.separator();
+        if ui.button(Text::CloseOthers.get(self.settings.language)).clicked() {
+            *self.close_tabs = Some(CloseTabs::Others(tab.node.0));
+            ui.close_menu();
+        }
+        if ui.button(Text::CloseToTheRight.get(self.settings.language)).clicked() {
+            *self.close_tabs = Some(CloseTabs::ToTheRight(tab.node.0));
+            ui.close_menu();
+        }
+        if ui.button(Text::CloseAll.get(self.settings.language)).clicked() {
+            *self.close_tabs = Some(CloseTabs::All);
+            ui.close_menu();
+        }
+    }
+
+    fn add_popup(&mut self, ui: &mut egui::Ui, surface: SurfaceIndex, node: NodeIndex) {
+        ui.set_min_width(80.0);
+        ui.style_mut().visuals.button_frame = false;
+
+        if ui.button(Text::CartesianTab.get(self.settings.language)).clicked() {
+            self.added_nodes.push(MyTab::cartesian(surface, node));
+        }
+        if ui.button(Text::PolarTab.get(self.settings.language)).clicked() {
+            self.added_nodes.push(MyTab::polar(surface, node));
+        }
+        if ui.button(Text::RangeTable.get(self.settings.language)).clicked() {
+            self.added_nodes.push(MyTab::range_table(surface, node));
+        }
+        if ui.button(Text::Salvo.get(self.settings.language)).clicked() {
+            self.added_nodes.push(MyTab::salvo(surface, node));
+        }
+        if ui.button(Text::FireMission.get(self.settings.language)).clicked() {
+            self.added_nodes.push(MyTab::fire_mission(surface, node));
+        }
+        if ui.button(Text::FixedPitch.get(self.settings.language)).clicked() {
+            self.added_nodes.push(MyTab::forward(surface, node));
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum TabDensity {
+    Compact,
+    Normal,
+    Roomy,
+}
+
+impl TabDensity {
+    fn label(&self) -> Text {
+        match self {
+            TabDensity::Compact => Text::Compact,
+            TabDensity::Normal  => Text::Normal,
+            TabDensity::Roomy   => Text::Roomy,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum Theme {
+    Dark,
+    Light,
+}
+
+impl Theme {
+    fn label(&self) -> Text {
+        match self {
+            Theme::Dark  => Text::Dark,
+            Theme::Light => Text::Light,
+        }
+    }
+
+    fn visuals(&self) -> egui::Visuals {
+        match self {
+            Theme::Dark  => egui::Visuals::dark(),
+            Theme::Light => egui::Visuals::light(),
+        }
+    }
+}
+
+//Unit typed angles are read in and solutions are shown in, the solver always works in radians
+//Elevation limits and steps describe the mount and stay in degrees, as do the command line, CSV export and copied text
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+enum AngleUnit {
+    Degrees,
+    Radians,
+}
+
+impl AngleUnit {
+    fn label(&self) -> Text {
+        match self {
+            AngleUnit::Degrees => Text::Degrees,
+            AngleUnit::Radians => Text::Radians,
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            AngleUnit::Degrees => "°",
+            AngleUnit::Radians => " rad",
+        }
+    }
+
+    fn to_radians(self, angle: f64) -> f64 {
+        match self {
+            AngleUnit::Degrees => angle.to_radians(),
+            AngleUnit::Radians => angle,
+        }
+    }
+
+    fn to_unit(self, radians: f64) -> f64 {
+        match self {
+            AngleUnit::Degrees => radians.to_degrees(),
+            AngleUnit::Radians => radians,
+        }
+    }
+
+    //Typed heading wrapped into [0, 2π), so -90° and 270° aim the same way
+    fn heading(self, angle: f64) -> f64 {
+        self.to_radians(angle).rem_euclid(TAU)
+    }
+
+    fn format(self, radians: f64, decimals: usize) -> String {
+        format!("{:.*}{}", decimals, self.to_unit(radians), self.symbol())
+    }
+
+    //Signed, for corrections and differences
+    fn format_offset(self, radians: f64, decimals: usize) -> String {
+        format!("{:+.*}{}", decimals, self.to_unit(radians), self.symbol())
+    }
+}
+
+//Languages the UI can be shown in, the command line and CSV export stay in English
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+enum Language {
+    English,
+    French,
+    German,
+}
+
+impl Language {
+    //Each language is listed under its own name
+    fn label(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::French  => "Français",
+            Language::German  => "Deutsch",
+        }
+    }
+}
+
+//Keys of the translated UI strings
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Text {
+    Cartesian,
+    Polar,
+    RangeTable,
+    Salvo,
+    FireMission,
+    FixedPitch,
+    CartesianTab,
+    PolarTab,
+    Cannon,
+    Target,
+    VacuumArc,
+    TargetOffset,
+    AtOrigin,
+    PasteCoords,
+    ReplaceCannon,
+    ReplaceTarget,
+    Replace,
+    Cancel,
+    Calculate,
+    Clear,
+    Live,
+    AmmoType,
+    PowderCharges,
+    Custom,
+    DirectShot,
+    IndirectShot,
+    Yaw,
+    Pitch,
+    InGame,
+    FlightTime,
+    Ticks,
+    ImpactAngle,
+    ImpactSpeed,
+    Distance,
+    Height,
+    Blocks,
+    MinCharges,
+    UnreachableElevation,
+    Prefer,
+    Flat,
+    Lobbed,
+    MaximumRangeShot,
+    Analytic,
+    Steps,
+    BarrelCorrection,
+    AimError,
+    Range,
+    NeverReachesHeight,
+    Despawns,
+    LongFlight,
+    Fuze,
+    Sensitive,
+    ClearsObstacle,
+    BlockedByObstacle,
+    SafeOverFriendlies,
+    DangerToFriendlies,
+    TerrainBothClear,
+    TerrainIndirectOnly,
+    TerrainBlocked,
+    Apex,
+    ApexAboveBuildHeight,
+    LeadYaw,
+    LeadPitch,
+    LeadNotConverged,
+    SolverDetails,
+    Method,
+    Iterations,
+    CopyDirect,
+    CopyIndirect,
+    ExportReport,
+    MaxRange,
+    UncalibratedAmmo,
+    WantedImpact,
+    BelowHorizontal,
+    ClosestImpact,
+    LandsAt,
+    Away,
+    TargetHeight,
+    Obstacle,
+    Terrain,
+    Friendlies,
+    OutOfRange,
+    NoConvergence,
+    InvalidTarget,
+    InvalidCharges,
+    InvalidAmmo,
+    SamePosition,
+    Shot,
+    APShot,
+    APShell,
+    HEShell,
+    MortarStone,
+    SmokeShell,
+    View,
+    Settings,
+    Language,
+    CannonTip,
+    TargetTip,
+    TargetOffsetTip,
+    AmmoTip,
+    ChargesTip,
+    YawTip,
+    DirectTip,
+    IndirectTip,
+    MergedTip,
+    SnappedPitch,
+    SnappedSame,
+    DuplicateBoth,
+    DuplicateCannon,
+    DuplicateTarget,
+    Dismiss,
+    TargetVelocity,
+    RelativeToFacing,
+    Search,
+    OutsideWorld,
+    To,
+    Rename,
+    Duplicate,
+    CloseOthers,
+    CloseToTheRight,
+    CloseAll,
+    Compact,
+    Normal,
+    Roomy,
+    Dark,
+    Light,
+    Degrees,
+    Radians,
+    TabDensity,
+    Theme,
+    Angles,
+    InGameAngles,
+    Steppers,
+    Overview,
+    ConfirmPaste,
+    BuildHeight,
+    WorldBottom,
+    GravityMultiplier,
+    Solver,
+    TickSimulation,
+    MaxSafeFlightTime,
+    FriendlyClearance,
+    DecimalPlaces,
+    Ammo,
+    SaveAmmoProfiles,
+    LoadAmmoProfiles,
+    ResetBuiltIn,
+    ExportCsv,
+    Tab,
+    FacingYaw,
+    Forward,
+    Right,
+    Up,
+    Azimuth,
+    BarrelLength,
+    EnterNumber,
+    NotANumber,
+    EnterDistance,
+    NegativeBarrel,
+    Charges,
+    FireAt,
+    ImpactAfter,
+    AfterFirstShot,
+    NoCannons,
+    RemoveTarget,
+    AddTarget,
+    PasteNewTarget,
+    EnterTargetCoords,
+    FixedTargetY,
+    EnterValidPitch,
+    NeverComesDown,
+    UpToCharges,
+    Velocity,
+    FlatRange,
+    TabName,
+    Cannons,
+    Name,
+    SaveCurrentAs,
+    ForgetCannon,
+    Log,
+    CopyLog,
+    ClearLog,
+    Drag,
+    Gravity,
+    CalibrateAmmo,
+    CalibrationHelp,
+    LandedX,
+    RemoveShot,
+    AddShot,
+    Fit,
+    VelocityPerCharge,
+    Residual,
+    ApplyTo,
+    NotEnoughShots,
+    NoFit,
+    CurrentAim,
+    TurnLeft,
+    TurnRight,
+    TurnDown,
+    TurnUp,
+    ElevationLimits,
+    BarrelBlocks,
+    ElevationStep,
+    PerStep,
+    BlocksAway,
+    AboveCannon,
+    AddPoint,
+    RemovePoint,
+    AddPosition,
+    RemovePosition,
+    ExportedTo,
+    ExportFailed,
+    SavedProfilesTo,
+    SaveProfilesFailed,
+    LoadedProfiles,
+    LoadProfilesFailed,
+    EnterNumberTip,
+}
+
+impl Text {
+    fn get(self, language: Language) -> &'static str {
+        let [english, french, german] = match self {
+            Text::Cartesian            => ["Cartesian", "Cartésien", "Kartesisch"],
+            Text::Polar                => ["Polar", "Polaire", "Polar"],
+            Text::RangeTable           => ["Range Table", "Table de tir", "Schusstafel"],
+            Text::Salvo                => ["Salvo", "Salve", "Salve"],
+            Text::FireMission          => ["Fire Mission", "Mission de tir", "Feuerauftrag"],
+            Text::FixedPitch           => ["Fixed Pitch", "Hausse fixe", "Feste Elevation"],
+            Text::CartesianTab         => ["Cartesian Tab", "Onglet cartésien", "Kartesischer Tab"],
+            Text::PolarTab             => ["Polar Tab", "Onglet polaire", "Polarer Tab"],
+            Text::Cannon               => ["Cannon", "Canon", "Kanone"],
+            Text::Target               => ["Target", "Cible", "Ziel"],
+            Text::VacuumArc            => ["Without drag", "Sans traînée", "Ohne Luftwiderstand"],
+            Text::TargetOffset         => ["Target offset", "Décalage de la cible", "Zielversatz"],
+            Text::AtOrigin             => ["At origin", "À l'origine", "Im Ursprung"],
+            Text::PasteCoords          => ["Paste coords", "Coller les coords", "Koords einfügen"],
+            Text::ReplaceCannon        => ["Replace the cannon coordinates with", "Remplacer les coordonnées du canon par",
+                                           "Kanonenkoordinaten ersetzen durch"],
+            Text::ReplaceTarget        => ["Replace the target coordinates with", "Remplacer les coordonnées de la cible par",
+                                           "Zielkoordinaten ersetzen durch"],
+            Text::Replace              => ["Replace", "Remplacer", "Ersetzen"],
+            Text::Cancel               => ["Cancel", "Annuler", "Abbrechen"],
+            Text::Calculate            => ["Calculate", "Calculer", "Berechnen"],
+            Text::Clear                => ["Clear", "Effacer", "Leeren"],
+            Text::Live                 => ["Live", "En direct", "Live"],
+            Text::AmmoType             => ["Ammo type", "Munition", "Munitionstyp"],
+            Text::PowderCharges        => ["Powder charges", "Charges de poudre", "Pulverladungen"],
+            Text::Custom               => ["Custom", "Personnalisé", "Benutzerdefiniert"],
+            Text::DirectShot           => ["Direct Shot", "Tir direct", "Direktschuss"],
+            Text::IndirectShot         => ["Indirect Shot", "Tir indirect", "Steilschuss"],
+            Text::Yaw                  => ["Yaw", "Lacet", "Gierwinkel"],
+            Text::Pitch                => ["Pitch", "Élévation", "Höhenwinkel"],
+            Text::InGame               => ["in-game", "en jeu", "im Spiel"],
+            Text::FlightTime           => ["Flight time", "Temps de vol", "Flugzeit"],
+            Text::Ticks                => ["ticks", "ticks", "Ticks"],
+            Text::ImpactAngle          => ["Impact angle", "Angle d'impact", "Einschlagwinkel"],
+            Text::ImpactSpeed          => ["Impact speed", "Vitesse d'impact", "Einschlaggeschwindigkeit"],
+            Text::Distance             => ["Distance", "Distance", "Entfernung"],
+            Text::Height               => ["Height", "Hauteur", "Höhe"],
+            Text::Blocks               => ["blocks", "blocs", "Blöcke"],
+            Text::MinCharges           => ["Min charges", "Charges min.", "Min. Ladungen"],
+            Text::UnreachableElevation => ["UNREACHABLE ELEVATION", "ÉLÉVATION HORS D'ATTEINTE", "HÖHENWINKEL UNERREICHBAR"],
+            Text::Prefer               => ["Prefer", "Préférer", "Bevorzugen"],
+            Text::Flat                 => ["Flat", "Tendu", "Flach"],
+            Text::Lobbed               => ["Lobbed", "En cloche", "Steil"],
+            Text::MaximumRangeShot     => ["Maximum range — only one solution", "Portée maximale — une seule solution", "Maximale Reichweite — nur eine Lösung"],
+            Text::Analytic             => ["Analytic", "Analytique", "Analytisch"],
+            Text::Steps                => ["steps", "crans", "Stufen"],
+            Text::BarrelCorrection     => ["Barrel correction", "Correction du fût", "Rohrkorrektur"],
+            Text::AimError             => ["Test-fire error", "Écart au tir d'essai", "Abweichung beim Probeschuss"],
+            Text::Range                => ["range", "de portée", "Reichweite"],
+            Text::NeverReachesHeight   => ["never reaches the target's height", "n'atteint jamais la hauteur de la cible", "erreicht nie die Zielhöhe"],
+            Text::Despawns             => ["Projectile despawns before impact", "Le projectile disparaît avant l'impact", "Das Geschoss verschwindet vor dem Einschlag"],
+            Text::LongFlight           => ["Long flight, the target's chunk may unload before impact", "Vol long, le chunk de la cible peut se décharger avant l'impact",
+                                           "Langer Flug, der Chunk des Ziels kann vor dem Einschlag entladen werden"],
+            Text::Fuze                 => ["Fuze", "Fusée", "Zünder"],
+            Text::Sensitive            => ["Solution is sensitive — small aiming errors cause large misses", "Solution sensible — de petites erreurs de visée causent de grands écarts",
+                                           "Empfindliche Lösung — kleine Zielfehler verursachen große Abweichungen"],
+            Text::ClearsObstacle       => ["Clears obstacle", "Passe l'obstacle", "Überwindet das Hindernis"],
+            Text::BlockedByObstacle    => ["Blocked by obstacle", "Bloqué par l'obstacle", "Vom Hindernis blockiert"],
+            Text::SafeOverFriendlies   => ["Safe over friendlies, lowest pass", "Sans danger pour les alliés, passage le plus bas", "Sicher über Verbündeten, niedrigster Überflug"],
+            Text::DangerToFriendlies   => ["Danger to friendlies, lowest pass", "Danger pour les alliés, passage le plus bas", "Gefahr für Verbündete, niedrigster Überflug"],
+            Text::TerrainBothClear     => ["Both arcs clear the terrain, the direct shot is the lowest", "Les deux trajectoires passent le terrain, le tir direct est le plus bas",
+                                           "Beide Flugbahnen überwinden das Gelände, der Direktschuss ist der niedrigste"],
+            Text::TerrainIndirectOnly  => ["Only the indirect shot clears the terrain", "Seul le tir indirect passe le terrain", "Nur der Steilschuss überwindet das Gelände"],
+            Text::TerrainBlocked       => ["No clearing solution over the terrain", "Aucune solution ne passe le terrain", "Keine Lösung überwindet das Gelände"],
+            Text::Apex                 => ["Apex", "Apogée", "Scheitelpunkt"],
+            Text::ApexAboveBuildHeight => ["Apex is above the build height, the projectile may despawn", "L'apogée dépasse la hauteur de construction, le projectile peut disparaître",
+                                           "Der Scheitelpunkt liegt über der Bauhöhe, das Geschoss kann verschwinden"],
+            Text::LeadYaw              => ["Lead yaw", "Lacet avec anticipation", "Gierwinkel mit Vorhalt"],
+            Text::LeadPitch            => ["Lead pitch", "Élévation avec anticipation", "Höhenwinkel mit Vorhalt"],
+            Text::LeadNotConverged     => ["Lead did not converge, showing the static solution", "L'anticipation n'a pas convergé, solution statique affichée",
+                                           "Vorhalt nicht konvergiert, die statische Lösung wird angezeigt"],
+            Text::SolverDetails        => ["Solver details", "Détails du solveur", "Solver-Details"],
+            Text::Method               => ["Method", "Méthode", "Methode"],
+            Text::Iterations           => ["Iterations", "Itérations", "Iterationen"],
+            Text::CopyDirect           => ["Copy direct", "Copier le direct", "Direktschuss kopieren"],
+            Text::CopyIndirect         => ["Copy indirect", "Copier l'indirect", "Steilschuss kopieren"],
+            Text::ExportReport         => ["Export report", "Exporter le rapport", "Bericht exportieren"],
+            Text::MaxRange             => ["Max range", "Portée max.", "Max. Reichweite"],
+            Text::UncalibratedAmmo     => ["Drag and velocity are placeholders, not the mod's values, until fitted from test shots",
+                                           "Traînée et vitesse provisoires, pas celles du mod, tant qu'elles ne sont pas ajustées par des tirs d'essai",
+                                           "Luftwiderstand und Geschwindigkeit sind Platzhalter, nicht die Werte des Mods, bis sie aus Probeschüssen angepasst werden"],
+            Text::WantedImpact         => ["Wanted impact", "Impact voulu", "Gewünschter Einschlag"],
+            Text::BelowHorizontal      => ["° below horizontal", "° sous l'horizontale", "° unter der Horizontalen"],
+            Text::ClosestImpact        => ["Closest impact", "Impact le plus proche", "Nächster Einschlag"],
+            Text::LandsAt              => ["Lands at", "Retombe en", "Landet bei"],
+            Text::Away                 => ["away", "de distance", "entfernt"],
+            Text::TargetHeight         => ["Target height", "Hauteur de la cible", "Zielhöhe"],
+            Text::Obstacle             => ["Obstacle", "Obstacle", "Hindernis"],
+            Text::Terrain              => ["Terrain", "Terrain", "Gelände"],
+            Text::Friendlies           => ["Friendlies", "Alliés", "Verbündete"],
+            Text::OutOfRange           => ["OUT OF RANGE", "HORS DE PORTÉE", "AUSSER REICHWEITE"],
+            Text::NoConvergence        => ["NO CONVERGENCE", "PAS DE CONVERGENCE", "KEINE KONVERGENZ"],
+            Text::InvalidTarget        => ["Enter a valid target position", "Entrez une position de cible valide", "Gültige Zielposition eingeben"],
+            Text::InvalidCharges       => ["Enter at least 1 charge", "Entrez au moins 1 charge", "Mindestens 1 Ladung eingeben"],
+            Text::InvalidAmmo          => ["Enter a non-negative drag and a positive gravity", "Entrez une traînée positive ou nulle et une gravité positive", "Nicht-negativen Luftwiderstand und positive Schwerkraft eingeben"],
+            Text::SamePosition         => ["Target equals cannon", "La cible est le canon", "Ziel und Kanone sind gleich"],
+            Text::Shot                 => ["Shot", "Boulet", "Vollgeschoss"],
+            Text::APShot               => ["AP Shot", "Boulet perforant", "Panzerbrechendes Vollgeschoss"],
+            Text::APShell              => ["AP Shell", "Obus perforant", "Panzergranate"],
+            Text::HEShell              => ["HE Shell", "Obus explosif", "Sprenggranate"],
+            Text::MortarStone          => ["Mortar Stone", "Pierre de mortier", "Mörserstein"],
+            Text::SmokeShell           => ["Smoke Shell", "Obus fumigène", "Nebelgranate"],
+            Text::View                 => ["View", "Affichage", "Ansicht"],
+            Text::Settings             => ["Settings", "Paramètres", "Einstellungen"],
+            Text::Language             => ["Language", "Langue", "Sprache"],
+            Text::CannonTip            => ["Block coordinates of the cannon mount, as shown on the F3 screen. Y is the height",
+                                           "Coordonnées du bloc du canon, telles qu'affichées par F3. Y est la hauteur",
+                                           "Blockkoordinaten der Kanone, wie im F3-Bildschirm angezeigt. Y ist die Höhe"],
+            Text::TargetTip            => ["Block coordinates of the target, as shown on the F3 screen. Y is the height of the block to hit",
+                                           "Coordonnées du bloc visé, telles qu'affichées par F3. Y est la hauteur du bloc à toucher",
+                                           "Blockkoordinaten des Ziels, wie im F3-Bildschirm angezeigt. Y ist die Höhe des zu treffenden Blocks"],
+            Text::TargetOffsetTip      => ["Blocks from the cannon to the target: +X east, +Y up, +Z south",
+                                           "Blocs du canon à la cible : +X vers l'est, +Y vers le haut, +Z vers le sud",
+                                           "Blöcke von der Kanone zum Ziel: +X nach Osten, +Y nach oben, +Z nach Süden"],
+            Text::AmmoTip              => ["Projectile to fire. Its drag and gravity shape the arc",
+                                           "Projectile tiré. Sa traînée et sa gravité façonnent la trajectoire",
+                                           "Abgefeuertes Geschoss. Sein Luftwiderstand und seine Schwerkraft formen die Flugbahn"],
+            Text::ChargesTip           => ["Powder charges behind the projectile. More charges fire it faster and farther",
+                                           "Charges de poudre derrière le projectile. Plus de charges le tirent plus vite et plus loin",
+                                           "Pulverladungen hinter dem Geschoss. Mehr Ladungen feuern es schneller und weiter"],
+            Text::YawTip               => ["Yaw follows the F3 screen: 0° faces south (+Z), 90° west, 180° north, 270° east",
+                                           "Le lacet suit l'écran F3 : 0° vers le sud (+Z), 90° vers l'ouest, 180° vers le nord, 270° vers l'est",
+                                           "Der Gierwinkel folgt dem F3-Bildschirm: 0° nach Süden (+Z), 90° nach Westen, 180° nach Norden, 270° nach Osten"],
+            Text::DirectTip            => ["Low, flat arc with the shortest flight. Prefer it when nothing is in the way",
+                                           "Trajectoire basse et tendue, le vol le plus court. À préférer quand rien ne gêne",
+                                           "Flache, gestreckte Flugbahn mit der kürzesten Flugzeit. Vorzuziehen, wenn nichts im Weg ist"],
+            Text::IndirectTip          => ["High, lobbed arc that drops onto the target from above. Prefer it over walls and hills or behind cover, at the cost of a longer flight",
+                                           "Trajectoire haute en cloche qui retombe sur la cible. À préférer par-dessus murs et collines ou derrière un abri, au prix d'un vol plus long",
+                                           "Hohe Steilfeuer-Flugbahn, die von oben auf das Ziel fällt. Vorzuziehen über Mauern und Hügel oder hinter Deckung, auf Kosten einer längeren Flugzeit"],
+            Text::MergedTip            => ["At maximum range the flat and lobbed arcs meet, only this pitch reaches the target",
+                                           "À portée maximale les deux trajectoires se rejoignent, seul cet angle atteint la cible",
+                                           "Bei maximaler Reichweite treffen sich beide Flugbahnen, nur dieser Winkel erreicht das Ziel"],
+            Text::SnappedPitch         => ["Snapped pitch", "Angle arrondi", "Gerundeter Winkel"],
+            Text::SnappedSame          => ["Both solutions snap to the same angle", "Les deux solutions tombent sur le même cran",
+                                           "Beide Lösungen rasten auf denselben Winkel ein"],
+            Text::DuplicateBoth        => ["Cannon and target have two identical coordinates, double-check them",
+                                           "Le canon et la cible ont deux coordonnées identiques, vérifiez-les",
+                                           "Kanone und Ziel haben zwei gleiche Koordinaten, bitte prüfen"],
+            Text::DuplicateCannon      => ["Cannon has two identical coordinates, double-check them",
+                                           "Le canon a deux coordonnées identiques, vérifiez-les",
+                                           "Die Kanone hat zwei gleiche Koordinaten, bitte prüfen"],
+            Text::DuplicateTarget      => ["Target has two identical coordinates, double-check them",
+                                           "La cible a deux coordonnées identiques, vérifiez-les",
+                                           "Das Ziel hat zwei gleiche Koordinaten, bitte prüfen"],
+            Text::Dismiss              => ["Dismiss", "Ignorer", "Ausblenden"],
+            Text::TargetVelocity       => ["Target velocity", "Vitesse de la cible", "Zielgeschwindigkeit"],
+            Text::RelativeToFacing     => ["Relative to facing", "Selon l'orientation", "Relativ zur Blickrichtung"],
+            Text::Search               => ["Search", "Rechercher", "Suchen"],
+            Text::OutsideWorld         => ["Y is outside the world", "Y est hors du monde", "Y liegt außerhalb der Welt"],
+            Text::To                   => ["to", "à", "bis"],
+            Text::Rename               => ["Rename", "Renommer", "Umbenennen"],
+            Text::Duplicate            => ["Duplicate", "Dupliquer", "Duplizieren"],
+            Text::CloseOthers          => ["Close others", "Fermer les autres", "Andere schließen"],
+            Text::CloseToTheRight      => ["Close to the right", "Fermer à droite", "Rechts schließen"],
+            Text::CloseAll             => ["Close all", "Tout fermer", "Alle schließen"],
+            Text::Compact              => ["Compact", "Compact", "Kompakt"],
+            Text::Normal               => ["Normal", "Normal", "Normal"],
+            Text::Roomy                => ["Roomy", "Aéré", "Geräumig"],
+            Text::Dark                 => ["Dark", "Sombre", "Dunkel"],
+            Text::Light                => ["Light", "Clair", "Hell"],
+            Text::Degrees              => ["Degrees", "Degrés", "Grad"],
+            Text::Radians              => ["Radians", "Radians", "Bogenmaß"],
+            Text::TabDensity           => ["Tab density", "Densité des onglets", "Tab-Dichte"],
+            Text::Theme                => ["Theme", "Thème", "Design"],
+            Text::Angles               => ["Angles", "Angles", "Winkel"],
+            Text::InGameAngles         => ["In-game angles", "Angles du jeu", "Winkel im Spiel"],
+            Text::Steppers             => ["Number steppers for coordinates and charges",
+                                           "Compteurs pour les coordonnées et les charges",
+                                           "Zahlenregler für Koordinaten und Ladungen"],
+            Text::Overview             => ["Overview of all tabs", "Aperçu de tous les onglets", "Übersicht aller Tabs"],
+            Text::ConfirmPaste         => ["Ask before pasting over filled coordinates",
+                                           "Demander avant de coller sur des coordonnées remplies",
+                                           "Vor dem Überschreiben ausgefüllter Koordinaten fragen"],
+            Text::BuildHeight          => ["Build height", "Hauteur de construction", "Bauhöhe"],
+            Text::WorldBottom          => ["World bottom", "Bas du monde", "Weltboden"],
+            Text::GravityMultiplier    => ["Gravity multiplier", "Multiplicateur de gravité", "Schwerkraftfaktor"],
+            Text::Solver               => ["Solver", "Solveur", "Löser"],
+            Text::TickSimulation       => ["Tick simulation", "Simulation par tick", "Tick-Simulation"],
+            Text::MaxSafeFlightTime    => ["Max safe flight time", "Temps de vol sûr max.", "Max. sichere Flugzeit"],
+            Text::FriendlyClearance    => ["Friendly clearance", "Marge au-dessus des alliés", "Abstand zu Verbündeten"],
+            Text::DecimalPlaces        => ["Decimal places", "Décimales", "Nachkommastellen"],
+            Text::Ammo                 => ["Ammo", "Munitions", "Munition"],
+            Text::SaveAmmoProfiles     => ["Save ammo profiles", "Enregistrer les profils de munitions", "Munitionsprofile speichern"],
+            Text::LoadAmmoProfiles     => ["Load ammo profiles", "Charger les profils de munitions", "Munitionsprofile laden"],
+            Text::ResetBuiltIn         => ["Reset to built-in", "Rétablir les munitions d'origine", "Auf eingebaute zurücksetzen"],
+            Text::ExportCsv            => ["Export CSV", "Exporter en CSV", "CSV exportieren"],
+            Text::Tab                  => ["Tab", "Onglet", "Tab"],
+            Text::FacingYaw            => ["Facing yaw", "Lacet de visée", "Blickrichtung"],
+            Text::Forward              => ["Forward", "Devant", "Vorwärts"],
+            Text::Right                => ["Right", "Droite", "Rechts"],
+            Text::Up                   => ["Up", "Haut", "Oben"],
+            Text::Azimuth              => ["Azimuth", "Azimut", "Azimut"],
+            Text::BarrelLength         => ["Barrel length", "Longueur du fût", "Rohrlänge"],
+            Text::EnterNumber          => ["enter a number", "saisissez un nombre", "Zahl eingeben"],
+            Text::NotANumber           => ["is not a number", "n'est pas un nombre", "ist keine Zahl"],
+            Text::EnterDistance        => ["Enter the distance to the target",
+                                           "Saisissez la distance jusqu'à la cible",
+                                           "Entfernung zum Ziel eingeben"],
+            Text::NegativeBarrel       => ["Barrel length can't be negative",
+                                           "La longueur du fût ne peut pas être négative",
+                                           "Die Rohrlänge darf nicht negativ sein"],
+            Text::Charges              => ["Charges", "Charges", "Ladungen"],
+            Text::FireAt               => ["Fire at", "Tirer à", "Feuern bei"],
+            Text::ImpactAfter          => ["Impact", "Impact", "Einschlag"],
+            Text::AfterFirstShot       => ["after the first shot", "après le premier tir", "nach dem ersten Schuss"],
+            Text::NoCannons            => ["Save cannons from a Cartesian tab's Cannons menu to plan a salvo",
+                                           "Enregistrez des canons depuis le menu Canons d'un onglet cartésien pour préparer une salve",
+                                           "Speichere Kanonen über das Kanonen-Menü eines kartesischen Tabs, um eine Salve zu planen"],
+            Text::RemoveTarget         => ["Remove this target", "Retirer cette cible", "Dieses Ziel entfernen"],
+            Text::AddTarget            => ["Add target", "Ajouter une cible", "Ziel hinzufügen"],
+            Text::PasteNewTarget       => ["Paste as new target", "Coller comme nouvelle cible", "Als neues Ziel einfügen"],
+            Text::EnterTargetCoords    => ["Enter the target's coordinates", "Saisissez les coordonnées de la cible", "Zielkoordinaten eingeben"],
+            Text::FixedTargetY         => ["Target Y, the cannon's when empty",
+                                           "Y de la cible, celui du canon si vide",
+                                           "Ziel-Y, leer für das der Kanone"],
+            Text::EnterValidPitch      => ["Enter a valid pitch", "Saisissez une élévation valide", "Gültigen Höhenwinkel eingeben"],
+            Text::NeverComesDown       => ["The shot never comes down to", "Le tir ne redescend jamais à", "Der Schuss kommt nie herunter auf"],
+            Text::UpToCharges          => ["Up to charges", "Jusqu'à charges", "Bis Ladungen"],
+            Text::Velocity             => ["Velocity", "Vitesse", "Geschwindigkeit"],
+            Text::FlatRange            => ["Range", "Portée", "Reichweite"],
+            Text::TabName              => ["Tab name", "Nom de l'onglet", "Tab-Name"],
+            Text::Cannons              => ["Cannons", "Canons", "Kanonen"],
+            Text::Name                 => ["Name", "Nom", "Name"],
+            Text::SaveCurrentAs        => ["Save current as…", "Enregistrer la position sous…", "Aktuelle speichern als…"],
+            Text::ForgetCannon         => ["Forget this cannon", "Oublier ce canon", "Diese Kanone vergessen"],
+            Text::Log                  => ["Log", "Journal", "Protokoll"],
+            Text::CopyLog              => ["Copy log", "Copier le journal", "Protokoll kopieren"],
+            Text::ClearLog             => ["Clear log", "Vider le journal", "Protokoll leeren"],
+            Text::Drag                 => ["Drag", "Traînée", "Luftwiderstand"],
+            Text::Gravity              => ["Gravity", "Gravité", "Schwerkraft"],
+            Text::CalibrateAmmo        => ["Calibrate ammo", "Calibrer les munitions", "Munition kalibrieren"],
+            Text::CalibrationHelp      => ["Fire a few shots with different charges or pitches and enter where each landed to fit",
+                                           "Tirez quelques coups avec différentes charges ou élévations et saisissez leurs points d'impact pour ajuster",
+                                           "Gib nach einigen Schüssen mit verschiedenen Ladungen oder Winkeln die Einschlagpunkte ein, zum Anpassen von"],
+            Text::LandedX              => ["Landed X", "Impact X", "Einschlag X"],
+            Text::RemoveShot           => ["Remove this shot", "Retirer ce tir", "Diesen Schuss entfernen"],
+            Text::AddShot              => ["Add shot", "Ajouter un tir", "Schuss hinzufügen"],
+            Text::Fit                  => ["Fit", "Ajuster", "Anpassen"],
+            Text::VelocityPerCharge    => ["velocity per charge", "vitesse par charge", "Geschwindigkeit pro Ladung"],
+            Text::Residual             => ["Residual", "Résidu", "Restfehler"],
+            Text::ApplyTo              => ["Apply to", "Appliquer à", "Anwenden auf"],
+            Text::NotEnoughShots       => ["Enter at least 2 complete shots that landed away from the cannon",
+                                           "Saisissez au moins 2 tirs complets tombés loin du canon",
+                                           "Mindestens 2 vollständige Schüsse eingeben, die nicht bei der Kanone gelandet sind"],
+            Text::NoFit                => ["No drag and velocity fit these shots, check the pitches and landing points",
+                                           "Aucune traînée ni vitesse ne correspond à ces tirs, vérifiez les élévations et les points d'impact",
+                                           "Kein Luftwiderstand und keine Geschwindigkeit passen zu diesen Schüssen, Winkel und Einschlagpunkte prüfen"],
+            Text::CurrentAim           => ["Current aim", "Visée actuelle", "Aktuelle Ausrichtung"],
+            Text::TurnLeft             => ["left", "à gauche", "links"],
+            Text::TurnRight            => ["right", "à droite", "rechts"],
+            Text::TurnDown             => ["down", "vers le bas", "runter"],
+            Text::TurnUp               => ["up", "vers le haut", "hoch"],
+            Text::ElevationLimits      => ["Elevation limits", "Limites d'élévation", "Höhenbegrenzung"],
+            Text::BarrelBlocks         => ["Barrel blocks", "Blocs du fût", "Rohrblöcke"],
+            Text::ElevationStep        => ["Elevation step", "Cran d'élévation", "Höhenstufe"],
+            Text::PerStep              => ["° per step", "° par cran", "° pro Stufe"],
+            Text::BlocksAway           => ["blocks away,", "blocs de distance,", "Blöcke entfernt,"],
+            Text::AboveCannon          => ["blocks above the cannon", "blocs au-dessus du canon", "Blöcke über der Kanone"],
+            Text::AddPoint             => ["Add point", "Ajouter un point", "Punkt hinzufügen"],
+            Text::RemovePoint          => ["Remove this point", "Retirer ce point", "Diesen Punkt entfernen"],
+            Text::AddPosition          => ["Add position", "Ajouter une position", "Position hinzufügen"],
+            Text::RemovePosition       => ["Remove this position", "Retirer cette position", "Diese Position entfernen"],
+            Text::ExportedTo           => ["Exported to", "Exporté vers", "Exportiert nach"],
+            Text::ExportFailed         => ["Export failed", "Échec de l'export", "Export fehlgeschlagen"],
+            Text::SavedProfilesTo      => ["Saved ammo profiles to", "Profils de munitions enregistrés dans", "Munitionsprofile gespeichert unter"],
+            Text::SaveProfilesFailed   => ["Saving ammo profiles failed",
+                                           "Échec de l'enregistrement des profils de munitions",
+                                           "Speichern der Munitionsprofile fehlgeschlagen"],
+            Text::LoadedProfiles       => ["Ammo profiles loaded", "Profils de munitions chargés", "Munitionsprofile geladen"],
+            Text::LoadProfilesFailed   => ["Loading ammo profiles failed",
+                                           "Échec du chargement des profils de munitions",
+                                           "Laden der Munitionsprofile fehlgeschlagen"],
+            Text::EnterNumberTip       => ["Enter a number", "Saisissez un nombre", "Zahl eingeben"],
+        };
+        match language {
+            Language::English => english,
+            Language::French  => french,
+            Language::German  => german,
+        }
+    }
+}
+
+//The library's errors print in English, the tabs show them in the chosen language
+fn error_text(error: SolveError) -> Text {
+    match error {
+        SolveError::OutOfRange    => Text::OutOfRange,
+        SolveError::NoConvergence => Text::NoConvergence,
+        SolveError::InvalidInput(InputError::Target)       => Text::InvalidTarget,
+        SolveError::InvalidInput(InputError::Charges)      => Text::InvalidCharges,
+        SolveError::InvalidInput(InputError::Ammo)         => Text::InvalidAmmo,
+        SolveError::InvalidInput(InputError::SamePosition) => Text::SamePosition,
+    }
+}
+
+//Loaded profiles narrowed to those whose name contains the filter, ignoring case
+//The built-ins lead the registry and are always kept, so the filter can't hide the basics
+fn filter_ammo(registry: Vec<Ammo>, filter: &str, language: Language) -> Vec<Ammo> {
+    let filter: String = filter.trim().to_lowercase();
+    let builtin: usize = Ammo::builtin().len();
+    registry.into_iter()
+        .enumerate()
+        .filter(|(i, ammo)| *i < builtin || ammo_label(ammo, language).to_lowercase().contains(&filter))
+        .map(|(_, ammo)| ammo)
+        .collect()
+}
+
+//Built-in ammo keeps its English name as what identifies it, only what's shown is translated
+fn ammo_label(ammo: &Ammo, language: Language) -> String {
+    let text = match ammo.kind {
+        AmmoType::Shot        => Text::Shot,
+        AmmoType::APShot      => Text::APShot,
+        AmmoType::APShell     => Text::APShell,
+        AmmoType::HEShell     => Text::HEShell,
+        AmmoType::MortarStone => Text::MortarStone,
+        AmmoType::SmokeShell  => Text::SmokeShell,
+        AmmoType::Custom      => return ammo.name.clone(),
+    };
+    if ammo.name == text.get(Language::English) { text.get(language).to_string() } else { ammo.name.clone() }
+}
+
+//Named cannon position, kept as typed so recalling it fills the fields exactly
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+struct CannonBookmark {
+    name: String,
+    position: [String; 3],
+}
+
+//User preferences, persisted across sessions through eframe's storage
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct Settings {
+    tab_density: TabDensity,
+    theme: Theme,
+    game_angles: bool,
+    build_height: f64,
+    world_min_y: f64,
+    gravity_multiplier: f64,
+    solver: Solver,
+    max_safe_flight_time: f64,
+    friendly_clearance: f64,
+    angle_unit: AngleUnit,
+    steppers: bool,
+    overview: bool,
+    confirm_paste: bool,
+    ammo_profiles: Vec<Ammo>,
+    cannons: Vec<CannonBookmark>,
+    decimals: usize,
+    language: Language,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            tab_density: TabDensity::Normal,
+            theme: Theme::Dark,
+            game_angles: false,
+            build_height: 320.0,
+            world_min_y: -64.0,
+            gravity_multiplier: 1.0,
+            solver: Solver::Analytic,
+            max_safe_flight_time: 0.0,
+            friendly_clearance: 5.0,
+            angle_unit: AngleUnit::Degrees,
+            steppers: false,
+            overview: false,
+            confirm_paste: false,
+            ammo_profiles: Vec::new(),
+            cannons: Vec::new(),
+            decimals: 4,
+            language: Language::English,
+        }
+    }
+}
+
+impl Settings {
+    //Lobs in the air longer than the limit may land after the target's chunk has unloaded, 0 turns the check off
+    fn exceeds_safe_flight_time(&self, t: f64) -> bool {
+        self.max_safe_flight_time > 0.0 && t > self.max_safe_flight_time
+    }
+
+    //Hint for a typed Y outside the world, None while it's inside or not a number yet
+    fn world_height_hint(&self, y: &str) -> Option<String> {
+        let y: f64 = optional_f64(y, f64::NAN);
+        (y < self.world_min_y || y > self.build_height)
+            .then(|| format!("{} ({} {} {})", Text::OutsideWorld.get(self.language), self.world_min_y, Text::To.get(self.language), self.build_height))
+    }
+
+    //Saving under an existing name moves that cannon, an empty name gets a numbered one
+    fn save_cannon(&mut self, name: &str, position: [String; 3]) {
+        let name: String = match name.trim() {
+            "" => format!("Cannon {}", self.cannons.len() + 1),
+            name => name.to_string()
+        };
+        match self.cannons.iter_mut().find(|cannon| cannon.name == name) {
+            Some(cannon) => cannon.position = position,
+            None => self.cannons.push(CannonBookmark { name, position })
+        }
+    }
+}
+
+//Derive the dock style from the current egui style (so it follows the theme) and apply the tab bar density on top
+fn dock_style(style: &egui::Style, density: TabDensity) -> egui_dock::Style {
+    let mut dock_style = egui_dock::Style::from_egui(style);
+    let (tab_bar_height, tab_margin) = match density {
+        TabDensity::Compact => (18.0, 2.0),
+        TabDensity::Normal  => (24.0, 4.0),
+        TabDensity::Roomy   => (32.0, 8.0),
+    };
+    dock_style.tab_bar.height = tab_bar_height;
+    dock_style.tab.tab_body.inner_margin = Margin::same(tab_margin);
+    dock_style
+}
+
+//Window size and position in points, stored next to the settings so the next launch opens where the user left it
+const WINDOW_KEY: &str = "window";
+const DEFAULT_WINDOW_SIZE: [f32; 2] = [1000.0, 750.0];
+const MIN_WINDOW_SIZE: [f32; 2] = [400.0, 300.0];
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+struct WindowGeometry {
+    position: [f32; 2],
+    size: [f32; 2],
+}
+
+impl WindowGeometry {
+    //None while minimized or before the platform has reported the window's rects
+    fn from_viewport(viewport: &egui::ViewportInfo) -> Option<Self> {
+        if viewport.minimized == Some(true) {
+            return None;
+        }
+        let position = viewport.outer_rect?.min;
+        let size = viewport.inner_rect?.size();
+        Some(Self { position: [position.x, position.y], size: [size.x, size.y] })
+    }
+
+    //A corrupted or hand-edited entry shouldn't open a window too small to use
+    fn is_usable(&self) -> bool {
+        self.position.iter().chain(&self.size).all(|value| value.is_finite())
+            && self.size[0] >= MIN_WINDOW_SIZE[0] && self.size[1] >= MIN_WINDOW_SIZE[1]
+    }
+}
+
+struct MyApp {
+    dock_state: DockState<MyTab>,
+    counter: usize,
+    settings: Settings,
+    status: Option<String>,
+    window: Option<WindowGeometry>,
+}
+
+impl MyApp {
+    fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let mut app = Self::default();
+        if let Some(storage) = cc.storage {
+            app.settings = eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
+            //Sent before the first frame, the window is only shown once it has been applied
+            app.window = eframe::get_value(storage, WINDOW_KEY).filter(WindowGeometry::is_usable);
+            if let Some(window) = app.window {
+                cc.egui_ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(window.size.into()));
+                cc.egui_ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(window.position.into()));
+            }
+        }
+        app
+    }
+
+    //Tabs are matched by their number, the part of the title that never changes
+    fn close_tabs(&mut self, close: CloseTabs) {
+        match close {
+            CloseTabs::Others(keep) => self.dock_state.retain_tabs(|tab| tab.node.0 == keep),
+            CloseTabs::ToTheRight(from) => {
+                //Only the tabs after it in its own tab bar, other splits and windows are left alone
+                let Some((leaf, _)) = self.dock_state.iter_all_tabs().find(|(_, tab)| tab.node.0 == from) else { return };
+                let closing: Vec<usize> = self.dock_state.iter_all_tabs()
+                    .filter(|(location, _)| *location == leaf)
+                    .map(|(_, tab)| tab.node.0)
+                    .skip_while(|&number| number != from)
+                    .skip(1)
+                    .collect();
+                self.dock_state.retain_tabs(|tab| !closing.contains(&tab.node.0));
+            }
+            //Starts over like a fresh launch, nothing is left that the numbering could clash with
+            CloseTabs::All => {
+                self.dock_state = DockState::new(vec![MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1))]);
+                self.counter = 2;
+            }
+        }
+    }
+
+    //Makes the next or previous tab active and focused, across splits and windows
+    fn cycle_tab(&mut self, backwards: bool) {
+        let Some(current) = self.dock_state.find_active_focused().map(|(_, tab)| tab.node.0) else { return };
+        let order: Vec<usize> = self.dock_state.iter_all_tabs().map(|(_, tab)| tab.node.0).collect();
+        if let Some(next) = cycle_tab(&order, current, backwards) {
+            self.focus_tab(next);
+        }
+    }
+
+    //Brings the tab with the given number to the front of its tab bar and focuses it
+    fn focus_tab(&mut self, number: usize) {
+        if let Some((surface, node, tab)) = self.dock_state.find_tab_from(|tab| tab.node.0 == number) {
+            self.dock_state.set_active_tab((surface, node, tab));
+            self.dock_state.set_focused_node_and_surface((surface, node));
+        }
+    }
+
+    //One row per open tab, clicking a tab's name switches to it
+    fn overview_content(&mut self, ui: &mut egui::Ui) {
+        let rows: Vec<(usize, String, [String; 4])> = self.dock_state.iter_all_tabs()
+            .map(|(_, tab)| (tab.node.0, tab.title(self.settings.language), tab.overview_row(&self.settings)))
+            .collect();
+        let mut clicked: Option<usize> = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            Grid::new("overview")
+            .striped(true)
+            .min_col_width(60.0)
+            .show(ui, |ui| {
+                for header in [Text::Tab, Text::Target, Text::Ammo, Text::Yaw, Text::Pitch] {
+                    ui.label(RichText::new(header.get(self.settings.language)).strong());
+                }
+                ui.end_row();
+                for (number, title, row) in rows {
+                    if ui.selectable_label(false, title).clicked() {
+                        clicked = Some(number);
+                    }
+                    for cell in row {
+                        ui.label(cell);
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+        if let Some(number) = clicked {
+            self.focus_tab(number);
+        }
+    }
+
+    //Write the solutions of every open tab to a CSV file picked by the user
+    fn export_csv(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .set_file_name("solutions.csv")
+            .save_file()
+        else {
+            return;
+        };
+
+        let csv = solutions_csv(self.dock_state.iter_all_tabs().map(|(_, tab)| tab).filter(|tab| matches!(tab.kind, MyTabKind::Cartesian | MyTabKind::Polar)));
+        let language: Language = self.settings.language;
+        self.status = Some(match std::fs::write(&path, csv) {
+            Ok(()) => format!("{} {}", Text::ExportedTo.get(language), path.display()),
+            Err(error) => format!("{}: {}", Text::ExportFailed.get(language), error)
+        });
+    }
+
+    //Write every ammo profile on offer, built-ins included, so the file is a complete set to edit and share
+    fn save_ammo_profiles(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("JSON", &["json"])
+            .set_file_name("ammo_profiles.json")
+            .save_file()
+        else {
+            return;
+        };
+
+        let json = serde_json::to_string_pretty(&Ammo::registry(&self.settings.ammo_profiles)).map_err(|error| error.to_string());
+        let language: Language = self.settings.language;
+        self.status = Some(match json.and_then(|json| std::fs::write(&path, json).map_err(|error| error.to_string())) {
+            Ok(()) => format!("{} {}", Text::SavedProfilesTo.get(language), path.display()),
+            Err(error) => format!("{}: {}", Text::SaveProfilesFailed.get(language), error)
+        });
+    }
+
+    fn load_ammo_profiles(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("JSON", &["json"])
+            .pick_file()
+        else {
+            return;
+        };
+
+        let profiles = std::fs::read_to_string(&path).map_err(|error| error.to_string())
+            .and_then(|json| serde_json::from_str::<Vec<Ammo>>(&json).map_err(|error| error.to_string()));
+        let language: Language = self.settings.language;
+        self.status = Some(match profiles {
+            Ok(profiles) => {
+                let count = profiles.len();
+                self.settings.ammo_profiles = profiles;
+                format!("{}: {}", Text::LoadedProfiles.get(language), count)
+            }
+            Err(error) => format!("{}: {}", Text::LoadProfilesFailed.get(language), error)
+        });
+    }
+}
+
+impl Default for MyApp {
+    fn default() -> Self {
+        let tree = DockState::new(vec![
+            MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1)),
+        ]);
+
+        Self {
+            dock_state: tree,
+            counter: 2,
+            settings: Settings::default(),
+            status: None,
+            window: None,
+        }
+    }
+}
+
+impl eframe::App for MyApp {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, eframe::APP_KEY, &self.settings);
+        if let Some(window) = self.window {
+            eframe::set_value(storage, WINDOW_KEY, &window);
+        }
+    }
+
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        //Tracked every frame since save only gets the storage, a minimized window keeps the last geometry
+        if let Some(window) = ctx.input(|input| WindowGeometry::from_viewport(input.viewport())) {
+            self.window = Some(window);
+        }
+
+        //Applied before anything is drawn so the dock style below is derived from the chosen theme
+        ctx.set_visuals(self.settings.theme.visuals());
+
+        egui::TopBottomPanel::top("top-bar").show(ctx, |ui| {
+            egui::menu::bar(ui, |ui| {
+                let language: Language = self.settings.language;
+                ui.menu_button(Text::View.get(language), |ui| {
+                    ui.label(Text::TabDensity.get(language));
+                    for density in [TabDensity::Compact, TabDensity::Normal, TabDensity::Roomy] {
+                        ui.radio_value(&mut self.settings.tab_density, density, density.label().get(language));
+                    }
+                    ui.separator();
+                    ui.label(Text::Theme.get(language));
+                    for theme in [Theme::Dark, Theme::Light] {
+                        ui.radio_value(&mut self.settings.theme, theme, theme.label().get(language));
+                    }
+                    ui.separator();
+                    ui.label(Text::Angles.get(language));
+                    for unit in [AngleUnit::Degrees, AngleUnit::Radians] {
+                        ui.radio_value(&mut self.settings.angle_unit, unit, unit.label().get(language));
+                    }
+                    ui.checkbox(&mut self.settings.game_angles, Text::InGameAngles.get(language));
+                    ui.separator();
+                    //Text fields are better for pasting, steppers for nudging a value by dragging or scrolling
+                    ui.checkbox(&mut self.settings.steppers, Text::Steppers.get(language));
+                    ui.checkbox(&mut self.settings.overview, Text::Overview.get(language));
+                    ui.checkbox(&mut self.settings.confirm_paste, Text::ConfirmPaste.get(language));
+                });
+                ui.menu_button(Text::Language.get(language), |ui| {
+                    for language in [Language::English, Language::French, Language::German] {
+                        ui.radio_value(&mut self.settings.language, language, language.label());
+                    }
+                });
+                ui.menu_button(Text::Settings.get(language), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(Text::BuildHeight.get(language));
+                        ui.add(egui::DragValue::new(&mut self.settings.build_height).speed(1.0));
+                    });
+                    //Only used to flag coordinates, custom worlds can go past it
+                    ui.horizontal(|ui| {
+                        ui.label(Text::WorldBottom.get(language));
+                        ui.add(egui::DragValue::new(&mut self.settings.world_min_y).speed(1.0));
+                    });
+                    //For modpacks and dimensions that scale gravity, applies to every ammo type
+                    ui.horizontal(|ui| {
+                        ui.label(Text::GravityMultiplier.get(language));
+                        ui.add(egui::DragValue::new(&mut self.settings.gravity_multiplier).speed(0.01).range(0.01..=10.0));
+                    });
+                    //The simulation follows the mod's per-tick steps, slower but closer to where shells really land
+                    ui.horizontal(|ui| {
+                        ui.label(Text::Solver.get(language));
+                        ui.radio_value(&mut self.settings.solver, Solver::Analytic, Text::Analytic.get(language));
+                        ui.radio_value(&mut self.settings.solver, Solver::Simulation, Text::TickSimulation.get(language));
+                    });
+                    //Only lobs are checked, 0 leaves it off
+                    ui.horizontal(|ui| {
+                        ui.label(Text::MaxSafeFlightTime.get(language));
+                        ui.add(egui::DragValue::new(&mut self.settings.max_safe_flight_time).speed(0.5).range(0.0..=600.0).suffix(" s"));
+                    });
+                    //Arcs passing lower than this over a friendly position are flagged
+                    ui.horizontal(|ui| {
+                        ui.label(Text::FriendlyClearance.get(language));
+                        ui.add(egui::DragValue::new(&mut self.settings.friendly_clearance).speed(0.5).range(0.0..=100.0).suffix(format!(" {}", Text::Blocks.get(language))));
+                    });
+                    //Places shown for yaw, pitch, flight time and impact angle, the CSV export always uses 4
+                    ui.horizontal(|ui| {
+                        ui.label(Text::DecimalPlaces.get(language));
+                        ui.add(egui::DragValue::new(&mut self.settings.decimals).range(0..=10));
+                    });
+                });
+                ui.menu_button(Text::Ammo.get(language), |ui| {
+                    if ui.button(Text::SaveAmmoProfiles.get(language)).clicked() {
+                        ui.close_menu();
+                        self.save_ammo_profiles();
+                    }
+                    if ui.button(Text::LoadAmmoProfiles.get(language)).clicked() {
+                        ui.close_menu();
+                        self.load_ammo_profiles();
+                    }
+                    if ui.add_enabled(!self.settings.ammo_profiles.is_empty(), egui::Button::new(Text::ResetBuiltIn.get(language))).clicked() {
+                        ui.close_menu();
+                        self.settings.ammo_profiles.clear();
+                    }
+                });
+                if ui.button(Text::ExportCsv.get(language)).clicked() {
+                    self.export_csv();
+                }
+                if let Some(status) = &self.status {
+                    ui.label(status);
+                }
+            });
+        });
+
+        //Ctrl+Z and Ctrl+Y step through the focused tab's inputs, a focused text field keeps its own undo
+        if ctx.memory(|memory| memory.focused().is_none()) {
+            let redo: bool = ctx.input_mut(|input| {
+                input.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z))
+                    || input.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND, Key::Y))
+            });
+            //Checked after redo, Ctrl+Z also matches with Shift held
+            let undo: bool = ctx.input_mut(|input| input.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND, Key::Z)));
+            if let Some((_, tab)) = self.dock_state.find_active_focused() {
+                if redo {
+                    tab.redo();
+                } else if undo {
+                    tab.undo();
+                }
+            }
+        }
+
+        if self.settings.overview {
+            egui::TopBottomPanel::bottom("overview")
+            .resizable(true)
+            .show(ctx, |ui| self.overview_content(ui));
+        }
+
+        //Ctrl+Tab and Ctrl+Shift+Tab go through the tabs in dock order, whatever has focus
+        let backwards: bool = ctx.input_mut(|input| input.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::Tab)));
+        let forwards: bool = ctx.input_mut(|input| input.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND, Key::Tab)));
+        if backwards || forwards {
+            self.cycle_tab(backwards);
+        }
+
+        let mut added_nodes = Vec::new();
+        let mut close_tabs = None;
+        DockArea::new(&mut self.dock_state)
+            .show_add_buttons(true)
+            .show_add_popup(true)
+            .style(dock_style(ctx.style().as_ref(), self.settings.tab_density))
+            .show(
+                ctx,
+                &mut TabViewer {
+                    added_nodes: &mut added_nodes,
+                    close_tabs: &mut close_tabs,
+                    settings: &mut self.settings,
+                },
+            );
+
+        if let Some(close) = close_tabs {
+            self.close_tabs(close);
+        }
+        
+        added_nodes.drain(..).for_each(|node| {
+            self.dock_state
+                .set_focused_node_and_surface((node.surface, node.node));
+            //Everything else comes along as the popup or the duplicate action set it up
+            self.dock_state.push_to_focused_leaf(MyTab { node: NodeIndex(self.counter), ..node });
+            self.counter += 1;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use create_big_cannons_ballistics_calculator::ballistics::{find_critical_point, firing_solution, flight_time, FiringSolution};
+
+    #[test]
+    fn barrel_length() {
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        tab.distance = "200".to_string();
+        tab.charges = "2".to_string();
+        tab.barrel_length = "6".to_string();
+        tab.height = "10".to_string();
+        tab.calculate(&Settings::default());
+        assert!(tab.barrel_shift.direct_shot.abs() > 0.0);
+
+        //The pitches are solved from the muzzle, so the plotted arcs start there and still end on the target
+        let input = tab.solved.unwrap();
+        for pitch in [tab.pitch.direct_shot, tab.pitch.indirect_shot] {
+            let points = input.points(pitch);
+            let (first, last) = (points[0], points[points.len() - 1]);
+            assert!((first[0] - 6.0*pitch.cos()).abs() < 1e-9 && (first[1] - 6.0*pitch.sin()).abs() < 1e-9);
+            assert!((last[0] - 200.0).abs() < 1e-9 && (last[1] - 10.0).abs() < 1e-3);
+            assert!((input.height_at(200.0, pitch) - 10.0).abs() < 1e-3);
+        }
+        //Nothing is checked against the barrel itself, only once the shell is out of it
+        assert_eq!(clearance(input, tab.pitch.indirect_shot, [1.0, 0.0]), None);
+    }
+
+    #[test]
+    fn barrel_blocks() {
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        tab.distance = "200".to_string();
+        tab.charges = "6".to_string();
+        tab.calculate(&Settings::default());
+        let full = tab.pitch.direct_shot;
+        assert!(tab.barrel_capacity_text().is_none());
+
+        //A short barrel burns fewer charges, so the shot needs a higher pitch
+        tab.barrel_blocks = "3".to_string();
+        tab.calculate(&Settings::default());
+        assert!(tab.pitch.direct_shot > full);
+        assert_eq!(tab.solved.unwrap().velocity, Ammo::shot().muzzle_velocity(3, None));
+        assert!(tab.barrel_capacity_text().is_some());
+
+        tab.charges = "3".to_string();
+        assert!(tab.barrel_capacity_text().is_none());
+    }
+
+    #[test]
+    fn impact_goal() {
+        let settings = Settings::default();
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.t_x = "400".to_string();
+        tab.charges = "2".to_string();
+        tab.calculate(&settings);
+        assert!(tab.impact_choice.is_none());
+
+        //Typed as a positive plunge, the steep one takes the lob of a heavier load than the one in the charges field
+        tab.wanted_impact = "85".to_string();
+        assert!(tab.is_dirty(&settings));
+        tab.calculate(&settings);
+        let choice: ImpactChoice = tab.impact_choice.unwrap();
+        assert!(choice.indirect && choice.charges > 2);
+        assert!((choice.impact_angle + 85.0_f64.to_radians()).abs() < 1.0_f64.to_radians());
+        assert!(tab.impact_choice_text(&settings).unwrap().contains(&format!("Powder charges: {}, Indirect Shot", choice.charges)));
+
+        tab.t_x = "100000".to_string();
+        tab.calculate(&settings);
+        assert!(tab.impact_choice.is_none());
+    }
+
+    #[test]
+    fn polar_apex() {
+        let settings = Settings::default();
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        tab.distance = "50".to_string();
+        tab.charges = "1".to_string();
+        tab.calculate(&settings);
+        let apex: f64 = tab.apex(tab.pitch.indirect_shot);
+        assert!(apex < settings.build_height);
+
+        //The apex is absolute like the build height, so the polar tab adds its cannon's Y too
+        tab.c_y = "300".to_string();
+        tab.calculate(&settings);
+        assert_eq!(tab.apex(tab.pitch.indirect_shot), apex + 300.0);
+        assert!(tab.apex(tab.pitch.indirect_shot) > settings.build_height);
+
+        tab.c_y = "400".to_string();
+        tab.calculate(&settings);
+        assert_eq!(tab.problems, vec!["Cannon: Y is outside the world (-64 to 320)".to_string()]);
+    }
+
+    #[test]
+    fn vertical_targets() {
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.c_x = "10".to_string();
+        tab.c_y = "64".to_string();
+        tab.c_z = "-20".to_string();
+        tab.t_x = "10".to_string();
+        tab.t_y = "80".to_string();
+        tab.t_z = "-20".to_string();
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.solve_error, None);
+        assert_eq!(tab.pitch.direct_shot, TAU/4.0);
+        assert_eq!(tab.yaw, 0.0);
+        assert_eq!(tab.apex(tab.pitch.direct_shot), 80.0);
+
+        tab.t_y = "64".to_string();
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.solve_error, Some(SolveError::InvalidInput(InputError::SamePosition)));
+        assert_eq!(tab.solve_error.unwrap().to_string(), "Target equals cannon");
+    }
+
+    #[test]
+    fn obstacle_clearance() {
+        let shot: Ammo = Ammo::shot();
+        let v: f64 = shot.muzzle_velocity(4, None);
+        let solution: FiringSolution = firing_solution(200.0, 0.0, shot.drag, v, shot.gravity).unwrap();
+        let input = SolveInput { distance: 200.0, height: 0.0, drag: shot.drag, velocity: v, gravity: shot.gravity, barrel: 0.0 };
+        let direct: f64 = trajectory_height(100.0, shot.drag, v, shot.gravity, solution.pitch.direct_shot);
+
+        //A wall just under the flat arc at midrange is cleared, one just over it isn't, the lob clears both
+        assert_eq!(clears_obstacle(input, solution.pitch.direct_shot, [100.0, direct - 1.0]), Some(true));
+        assert_eq!(clears_obstacle(input, solution.pitch.direct_shot, [100.0, direct + 1.0]), Some(false));
+        assert_eq!(clears_obstacle(input, solution.pitch.indirect_shot, [100.0, direct + 1.0]), Some(true));
+
+        //Obstacles behind the cannon or past the target aren't in the way
+        assert_eq!(clears_obstacle(input, solution.pitch.direct_shot, [-5.0, 1000.0]), None);
+        assert_eq!(clears_obstacle(input, solution.pitch.direct_shot, [250.0, 1000.0]), None);
+
+        //Nothing is checked until both fields are filled
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.obstacle_distance = "100".to_string();
+        assert!(tab.obstacle().is_none());
+        tab.obstacle_height = "12.5".to_string();
+        assert_eq!(tab.obstacle(), Some([100.0, 12.5]));
+    }
+
+    #[test]
+    fn terrain_clearance() {
+        let shot: Ammo = Ammo::shot();
+        let v: f64 = shot.muzzle_velocity(4, None);
+        let solution: FiringSolution = firing_solution(200.0, 0.0, shot.drag, v, shot.gravity).unwrap();
+        let input = SolveInput { distance: 200.0, height: 0.0, drag: shot.drag, velocity: v, gravity: shot.gravity, barrel: 0.0 };
+        let direct: f64 = trajectory_height(100.0, shot.drag, v, shot.gravity, solution.pitch.direct_shot);
+        let indirect: f64 = trajectory_height(100.0, shot.drag, v, shot.gravity, solution.pitch.indirect_shot);
+
+        //Low ground is cleared by both, a ridge over the flat arc leaves only the lob, one over both leaves nothing
+        let low = [[50.0, 0.0], [100.0, direct - 1.0], [150.0, 0.0]];
+        assert_eq!(terrain_arc(input, solution.pitch, &low), Some(false));
+        assert_eq!(terrain_arc(input, solution.pitch, &[[50.0, 0.0], [100.0, direct + 1.0]]), Some(true));
+        assert_eq!(terrain_arc(input, solution.pitch, &[[100.0, indirect + 1.0]]), None);
+        //Points past the target don't block anything
+        assert_eq!(terrain_arc(input, solution.pitch, &[[300.0, 1000.0]]), Some(false));
+
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.terrain = vec![["50".to_string(), "3".to_string()], ["100".to_string(), "".to_string()]];
+        assert_eq!(tab.terrain_points(), vec![[50.0, 3.0]]);
+    }
+
+    #[test]
+    fn friendly_clearance() {
+        let shot: Ammo = Ammo::shot();
+        let v: f64 = shot.muzzle_velocity(4, None);
+        let solution: FiringSolution = firing_solution(200.0, 0.0, shot.drag, v, shot.gravity).unwrap();
+        let input = SolveInput { distance: 200.0, height: 0.0, drag: shot.drag, velocity: v, gravity: shot.gravity, barrel: 0.0 };
+        let direct: f64 = trajectory_height(50.0, shot.drag, v, shot.gravity, solution.pitch.direct_shot);
+
+        //The lowest pass is reported, friendlies behind the cannon or past the target are ignored
+        let friendlies = [[50.0, 0.0], [150.0, 0.0], [-10.0, 0.0], [250.0, 0.0]];
+        let (distance, clearance) = friendly_pass(input, solution.pitch.direct_shot, &friendlies).unwrap();
+        assert!(distance == 50.0 || distance == 150.0);
+        assert!(clearance <= direct + 1e-9);
+        assert!((friendly_pass(input, solution.pitch.direct_shot, &[[50.0, 2.0]]).unwrap().1 - (direct - 2.0)).abs() < 1e-9);
+        assert_eq!(friendly_pass(input, solution.pitch.direct_shot, &[[-10.0, 0.0]]), None);
+
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.friendlies = vec![["50".to_string(), "".to_string()], ["".to_string(), "3".to_string()]];
+        assert_eq!(tab.friendly_points(), vec![[50.0, 0.0]]);
+        [tab.t_x, tab.t_y, tab.t_z] = ["200".to_string(), "0".to_string(), "0".to_string()];
+        tab.charges = "4".to_string();
+        tab.calculate(&Settings::default());
+        //With the margin just above the flat shot's pass only the lob is safe
+        let ctx = egui::Context::default();
+        let _ = ctx.run(Default::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let settings = Settings { friendly_clearance: direct + 1.0, ..Settings::default() };
+                assert!(tab.friendly_text(tab.pitch.direct_shot, &settings, ui).unwrap().text().starts_with("Danger"));
+                assert!(tab.friendly_text(tab.pitch.indirect_shot, &settings, ui).unwrap().text().starts_with("Safe"));
+            });
+        });
+    }
+
+    #[test]
+    fn fixed_pitch_tab() {
+        let settings = Settings::default();
+        let mut tab = MyTab::forward(SurfaceIndex::main(), NodeIndex(1));
+        assert_eq!(tab.title(Language::English), "Fixed Pitch 1");
+        [tab.c_x, tab.c_y, tab.c_z] = ["10".to_string(), "64".to_string(), "-20".to_string()];
+        tab.charges = "4".to_string();
+
+        //Aimed at a cartesian solution it lands on that tab's target
+        let mut aimed = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(2));
+        [aimed.c_x, aimed.c_y, aimed.c_z] = [tab.c_x.clone(), tab.c_y.clone(), tab.c_z.clone()];
+        [aimed.t_x, aimed.t_y, aimed.t_z] = ["-90".to_string(), "70".to_string(), "80".to_string()];
+        aimed.charges = tab.charges.clone();
+        aimed.calculate(&settings);
+        tab.azimuth = aimed.yaw.to_degrees().to_string();
+        tab.table_pitch = aimed.pitch.indirect_shot.to_degrees().to_string();
+        tab.t_y = "70".to_string();
+        let landing: Landing = tab.landing(&settings).unwrap();
+        for (axis, expected) in [-90.0, 70.0, 80.0].into_iter().enumerate() {
+            assert!((landing.position[axis] - expected).abs() < 1e-6);
+        }
+        assert!((landing.time - aimed.time.indirect_shot).abs() < 1e-6);
+        assert!(landing.impact_angle < 0.0);
+
+        //An empty target Y lands level with the cannon
+        tab.t_y = "".to_string();
+        assert_eq!(tab.landing(&settings).unwrap().position[1], 64.0);
+        tab.t_y = "5000".to_string();
+        assert_eq!(tab.landing(&settings).unwrap_err(), "The shot never comes down to Y 5000");
+        tab.table_pitch = "".to_string();
+        assert_eq!(tab.landing(&settings).unwrap_err(), "Enter a valid pitch");
+    }
+
+    #[test]
+    fn dirty_check() {
+        let settings = Settings::default();
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        [tab.t_x, tab.t_y, tab.t_z] = ["200".to_string(), "0".to_string(), "0".to_string()];
+        tab.charges = "4".to_string();
+        assert!(tab.is_dirty(&settings));
+        tab.calculate(&settings);
+        assert!(!tab.is_dirty(&settings));
+
+        //Solving the same inputs again is skipped, the log shows it didn't run
+        tab.calculate(&settings);
+        assert_eq!(tab.log.len(), 1);
+        assert!(!tab.is_dirty(&settings));
+
+        tab.t_x = "201".to_string();
+        assert!(tab.is_dirty(&settings));
+        tab.t_x = "200".to_string();
+        assert!(!tab.is_dirty(&settings));
+
+        //Settings the solve reads count too, ones it doesn't leave the tab clean
+        assert!(tab.is_dirty(&Settings { gravity_multiplier: 2.0, ..Settings::default() }));
+        assert!(!tab.is_dirty(&Settings { decimals: 1, ..Settings::default() }));
+
+        tab.t_x = "150".to_string();
+        tab.calculate(&settings);
+        assert_eq!(tab.log.len(), 2);
+
+        //Every ammo value counts, not just the ones that move the shot
+        tab.ammo_type.despawn_ticks = 100;
+        assert!(tab.is_dirty(&settings));
+        tab.calculate(&settings);
+        assert_eq!(tab.log.len(), 3);
+        tab.ammo_type.max_charges = 4;
+        assert!(tab.is_dirty(&settings));
+        tab.clear();
+        assert!(tab.is_dirty(&settings));
+    }
+
+    #[test]
+    fn calibration_fit() {
+        let mut settings = Settings::default();
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        [tab.c_x, tab.c_y, tab.c_z] = ["100".to_string(), "64".to_string(), "100".to_string()];
+
+        //Shots landed by a shot with more drag and less velocity than the built-in values, fired east and south
+        let (u, k): (f64, f64) = (0.012, 38.0);
+        tab.calibration_shots = [(2, 20.0, 64.0, [1.0, 0.0]), (4, 35.0, 70.0, [0.0, 1.0]), (6, 15.0, 60.0, [1.0, 0.0])].into_iter()
+            .map(|(charges, pitch, y, [east, south]): (u32, f64, f64, [f64; 2])| {
+                let distance: f64 = landing_distance(y - 64.0, u, k * charges as f64, 10.0, pitch.to_radians()).unwrap();
+                [charges.to_string(), pitch.to_string(), (100.0 + east*distance).to_string(), y.to_string(), (100.0 + south*distance).to_string()]
+            })
+            .collect();
+        tab.calibration_shots.push(["3".to_string(), "".to_string(), "".to_string(), "".to_string(), "".to_string()]);
+        let fit: Calibration = tab.fit_calibration(&settings).unwrap();
+        assert!((fit.drag - u).abs() < 1e-6 && (fit.velocity_per_charge - k).abs() < 1e-4 && fit.residual < 1e-3);
+
+        //Applying it changes the ammo the next calculation uses and keeps it as a profile
+        [tab.t_x, tab.t_y, tab.t_z] = ["300".to_string(), "64".to_string(), "100".to_string()];
+        tab.charges = "4".to_string();
+        tab.calculate(&settings);
+        assert!(tab.ammo_type.is_uncalibrated());
+        tab.apply_calibration(fit, &mut settings);
+        assert!(!tab.ammo_type.is_uncalibrated());
+        assert!(tab.is_dirty(&settings));
+        assert_eq!(tab.ammo_type.drag, fit.drag);
+        assert_eq!(Ammo::registry(&settings.ammo_profiles)[0].velocity_per_charge, fit.velocity_per_charge);
+        tab.calculate(&settings);
+        let pitch: f64 = tab.pitch.direct_shot;
+        assert!((trajectory_height(200.0, u, 4.0*k, 10.0, pitch)).abs() < 1e-3);
+
+        tab.calibration_shots.truncate(1);
+        assert!(tab.fit_calibration(&settings).is_err());
+    }
+
+    #[test]
+    fn mount_steps() {
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        let settings = Settings { decimals: 1, ..Settings::default() };
+        assert!(tab.steps_text(0.5, &settings).is_none());
+        tab.step_size = "0".to_string();
+        assert!(tab.steps_text(0.5, &settings).is_none());
+        tab.step_size = "0.5".to_string();
+        assert_eq!(tab.steps_text(37.5_f64.to_radians(), &settings).unwrap(), "→ 75 steps (+0.0°)");
+
+        //Coarse steps leave the shot off target, a pitch right on a step doesn't
+        tab.t_x = "250".to_string();
+        tab.charges = "2".to_string();
+        tab.step_size = "5".to_string();
+        tab.calculate(&settings);
+        let error = tab.aim_error_text(tab.pitch.direct_shot, &settings).unwrap();
+        assert!(error.starts_with("Test-fire error: ") && !error.ends_with("0.0 blocks range, +0.0 blocks Y"));
+        assert!(tab.aim_error_text(40.0_f64.to_radians(), &settings).unwrap().ends_with("0.0 blocks range, +0.0 blocks Y"));
+    }
+
+    #[test]
+    fn paste_confirmation() {
+        let pasted = || ["10".to_string(), "64".to_string(), "-20".to_string()];
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        assert!(!would_overwrite(&tab.coordinate_fields(PasteTarget::Cannon)));
+        tab.t_y = " ".to_string();
+        assert!(!would_overwrite(&tab.coordinate_fields(PasteTarget::Target)));
+        tab.t_y = "5".to_string();
+        assert!(would_overwrite(&tab.coordinate_fields(PasteTarget::Target)));
+
+        //Off by default, filled fields are simply replaced
+        tab.paste_values(PasteTarget::Target, pasted(), &Settings::default());
+        assert_eq!([&tab.t_x, &tab.t_y, &tab.t_z], ["10", "64", "-20"]);
+        assert!(tab.pending_paste.is_none());
+
+        //Empty fields are filled straight away even when asking
+        let settings = Settings { confirm_paste: true, ..Settings::default() };
+        tab.paste_values(PasteTarget::Cannon, pasted(), &settings);
+        assert_eq!([&tab.c_x, &tab.c_y, &tab.c_z], ["10", "64", "-20"]);
+        assert!(tab.pending_paste.is_none());
+
+        //Filled ones wait for the prompt, cancelling leaves them as they were
+        tab.c_x = "1".to_string();
+        tab.paste_values(PasteTarget::Cannon, ["2".to_string(), "3".to_string(), "4".to_string()], &settings);
+        assert_eq!([&tab.c_x, &tab.c_y, &tab.c_z], ["1", "64", "-20"]);
+        assert!(tab.pending_paste.is_some());
+        tab.resolve_paste(false);
+        assert_eq!([&tab.c_x, &tab.c_y, &tab.c_z], ["1", "64", "-20"]);
+        assert!(tab.pending_paste.is_none());
+
+        tab.paste_values(PasteTarget::Cannon, ["2".to_string(), "3".to_string(), "4".to_string()], &settings);
+        tab.resolve_paste(true);
+        assert_eq!([&tab.c_x, &tab.c_y, &tab.c_z], ["2", "3", "4"]);
+        assert!(tab.pending_paste.is_none());
+    }
+
+    #[test]
+    fn vacuum_overlay() {
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.t_x = "400".to_string();
+        tab.t_y = "10".to_string();
+        tab.charges = "2".to_string();
+        tab.barrel_length = "3".to_string();
+        tab.calculate(&Settings::default());
+        let input: SolveInput = tab.solved.unwrap();
+
+        for pitch in [tab.pitch.direct_shot, tab.pitch.indirect_shot] {
+            let [mx, my] = input.muzzle(pitch);
+            let (v, g) = (input.velocity, input.gravity);
+            let vacuum: Vec<[f64; 2]> = input.vacuum_points(pitch);
+            //Every sample is the closed form parabola from the muzzle
+            for [x, y] in &vacuum {
+                let x: f64 = x - mx;
+                assert!((y - my - (x*pitch.tan() - g*x*x/(2.0*v*v*pitch.cos()*pitch.cos()))).abs() < 1e-9);
+            }
+            //Back down at the target's height, well past the target the drag arc stops at
+            let [end_x, end_y] = *vacuum.last().unwrap();
+            assert!((end_y - input.height).abs() < 1e-6 && end_x > input.distance + 1.0);
+            //And above the drag arc everywhere past the muzzle at the same pitch
+            for [x, y] in input.points(pitch).into_iter().skip(1) {
+                assert!(my + trajectory_height(x - mx, 0.0, v, g, pitch) > y);
+            }
+        }
+    }
+
+    #[test]
+    fn snapped_collapse() {
+        let settings = Settings::default();
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.t_x = "150".to_string();
+        tab.charges = "1".to_string();
+        tab.calculate(&settings);
+        assert!(tab.snapped_pitch.direct_shot.is_nan() && !tab.snapped_same());
+
+        //About 38° and 51°, a fine step keeps them apart
+        tab.step_size = "5".to_string();
+        assert!(tab.is_dirty(&settings));
+        tab.calculate(&settings);
+        assert!((tab.snapped_pitch.direct_shot - 40.0_f64.to_radians()).abs() < 1e-9);
+        assert!((tab.snapped_pitch.indirect_shot - 50.0_f64.to_radians()).abs() < 1e-9);
+        assert!(!tab.merged_shot() && !tab.snapped_same());
+
+        //A coarse one rounds both to 45°, one physical shot that isn't at maximum range
+        tab.step_size = "45".to_string();
+        tab.calculate(&settings);
+        assert_eq!(tab.snapped_pitch.direct_shot, tab.snapped_pitch.indirect_shot);
+        assert!(!tab.merged_shot() && tab.snapped_same());
+        assert_eq!(Text::SnappedSame.get(settings.language), "Both solutions snap to the same angle");
+        assert!(tab.aim_error_text(tab.pitch.direct_shot, &settings).is_some());
+    }
+
+    #[test]
+    fn solution_formatting() {
+        let text = solution_text("Direct shot", TAU/4.0, TAU/8.0, 2.5, -TAU/12.0, 4);
+        assert_eq!(text, "Direct shot\nYaw: 90.0000°\nPitch: 45.0000°\nFlight time: 2.5000s\nImpact angle: -30.0000°");
+
+        //The pitch is rounded like everything else instead of printing float noise
+        let settings = Settings { decimals: 2, ..Settings::default() };
+        assert_eq!(MyTab::pitch_text(TAU/8.0 + 1e-12, &settings), "Pitch: 45.00°");
+        assert_eq!(solution_text("Indirect shot", 0.0, TAU/6.0, 10.0, -TAU/5.0, 0), "Indirect shot\nYaw: 0°\nPitch: 60°\nFlight time: 10s\nImpact angle: -72°");
+    }
+
+    #[test]
+    fn custom_ammo() {
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        tab.distance = "100".to_string();
+        tab.calculate(&Settings::default());
+        let shot_pitch = tab.pitch.direct_shot;
+
+        tab.custom_name = "Heavy Shot".to_string();
+        tab.ammo_type = tab.custom_ammo();
+        tab.calculate(&Settings::default());
+        assert!(tab.ammo_type == Ammo::custom("Heavy Shot", 0.0, 0.0));
+        assert!((tab.pitch.direct_shot - shot_pitch).abs() < 1e-9);
+
+        tab.custom_gravity = "12".to_string();
+        tab.ammo_type = tab.custom_ammo();
+        tab.calculate(&Settings::default());
+        assert!(tab.pitch.direct_shot > shot_pitch);
+
+        tab.custom_gravity = "-10".to_string();
+        tab.ammo_type = tab.custom_ammo();
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.solve_error, Some(SolveError::InvalidInput(InputError::Ammo)));
+
+        tab.custom_gravity = "10".to_string();
+        tab.custom_drag = "".to_string();
+        tab.ammo_type = tab.custom_ammo();
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.solve_error, Some(SolveError::InvalidInput(InputError::Ammo)));
+    }
+
+    #[test]
+    fn minimum_charges() {
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        tab.distance = "200".to_string();
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.solve_error, Some(SolveError::OutOfRange));
+        assert_eq!(tab.min_charges, Some(2));
+    }
+
+    #[test]
+    fn csv_export() {
+        let mut solved = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        solved.t_x = "-100".to_string();
+        solved.calculate(&Settings::default());
+        let mut out_of_range = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(2));
+        out_of_range.t_z = "1000".to_string();
+        out_of_range.calculate(&Settings::default());
+
+        let csv = solutions_csv([&solved, &out_of_range].into_iter());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].starts_with(",,,-100,,,Shot,1,90.0000,"));
+        assert_eq!(lines[2], ",,,,,1000,Shot,1,0.0000,,,,,,");
+        for line in lines {
+            assert_eq!(line.split(',').count(), 15);
+        }
+
+        assert_eq!(csv_field("Heavy, Shot"), "\"Heavy, Shot\"");
+        assert_eq!(csv_field("12\" Shell"), "\"12\"\" Shell\"");
+    }
+
+    #[test]
+    fn moving_target_lead() {
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.t_x = "-100".to_string();
+        tab.calculate(&Settings::default());
+        assert!(tab.lead.direct_shot.is_none());
+
+        //Moving south the lead point is south of west, at the distance the projectile covers in its own flight time
+        tab.v_z = "5".to_string();
+        tab.calculate(&Settings::default());
+        let lead = tab.lead.direct_shot.unwrap();
+        assert!(lead.converged);
+        assert!(lead.yaw < tab.yaw);
+        let solved = tab.solved.unwrap();
+        let d = 100.0/lead.yaw.sin();
+        let t = flight_time(d, solved.drag, solved.velocity, lead.pitch);
+        assert!((d*lead.yaw.cos() - 5.0*t).abs() < 1e-2);
+        assert!(trajectory_height(d, solved.drag, solved.velocity, solved.gravity, lead.pitch).abs() < 1e-3);
+
+        //A target outrunning the projectile can't be led, the static solution is kept
+        tab.v_z = "1000".to_string();
+        tab.calculate(&Settings::default());
+        let lead = tab.lead.indirect_shot.unwrap();
+        assert!(!lead.converged);
+        assert_eq!(lead.pitch, tab.pitch.indirect_shot);
+    }
+
+    #[test]
+    fn relative_coordinates() {
+        let mut absolute = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        absolute.c_x = "1000".to_string();
+        absolute.c_y = "64".to_string();
+        absolute.c_z = "-500".to_string();
+        absolute.t_x = "1200".to_string();
+        absolute.t_y = "94".to_string();
+        absolute.t_z = "-450".to_string();
+        absolute.charges = "3".to_string();
+        absolute.calculate(&Settings::default());
+
+        let mut relative = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(2));
+        relative.c_x = "1000".to_string();
+        relative.relative = true;
+        relative.t_x = "200".to_string();
+        relative.t_y = "30".to_string();
+        relative.t_z = "50".to_string();
+        relative.charges = "3".to_string();
+        relative.calculate(&Settings::default());
+        assert!(relative.pitch.direct_shot.is_finite());
+
+        assert_eq!(relative.yaw, absolute.yaw);
+        assert_eq!(relative.pitch.direct_shot, absolute.pitch.direct_shot);
+        assert_eq!(relative.pitch.indirect_shot, absolute.pitch.indirect_shot);
+        assert_eq!(relative.apex(relative.pitch.indirect_shot) + 64.0, absolute.apex(absolute.pitch.indirect_shot));
+
+        //Switching back uses the cannon coordinates again, nothing was cleared
+        relative.relative = false;
+        assert_eq!(relative.c_x, "1000");
+        assert_eq!(relative.t_x, "200");
+    }
+
+    #[test]
+    fn target_offsets() {
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.c_x = "10".to_string();
+        tab.c_y = "70".to_string();
+        tab.t_x = "40".to_string();
+        tab.t_y = "60".to_string();
+        tab.t_z = "40".to_string();
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.target_distance, 50.0);
+        assert_eq!(tab.target_height, -10.0);
+
+        //Kept when the target is out of range, dropped when cleared
+        tab.t_z = "4000".to_string();
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.solve_error, Some(SolveError::OutOfRange));
+        assert!(tab.target_distance > 4000.0);
+        tab.clear();
+        assert!(tab.target_distance.is_nan());
+    }
+
+    #[test]
+    fn single_arc() {
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.t_x = "5".to_string();
+        tab.c_y = "100".to_string();
+        tab.t_y = "0".to_string();
+        tab.charges = "4".to_string();
+        tab.v_z = "1".to_string();
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.solve_error, None);
+        assert!(tab.pitch.direct_shot.is_finite() && tab.time.direct_shot.is_finite());
+        assert!(tab.pitch.indirect_shot.is_nan() && tab.time.indirect_shot.is_nan());
+        assert!(tab.lead.direct_shot.is_some() && tab.lead.indirect_shot.is_none());
+
+        //The other way round, a long barrel pokes past a target right next to the cannon and only the lob reaches it
+        let mut lob = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(2));
+        lob.t_x = "4".to_string();
+        lob.charges = "1".to_string();
+        lob.barrel_length = "3".to_string();
+        lob.max_elevation = "90".to_string();
+        lob.calculate(&Settings::default());
+        assert_eq!(lob.solve_error, None);
+        assert!(lob.pitch.direct_shot.is_nan() && lob.time.direct_shot.is_nan());
+        assert!(lob.pitch.indirect_shot.is_finite() && lob.time.indirect_shot.is_finite());
+        //Shown and picked even though the flat arc is preferred
+        assert_eq!(lob.preference, Preference::Flat);
+        assert_eq!(lob.preferred_shot(), Some(true));
+        assert!(lob.preferred_arc());
+        assert!(lob.mission_shot(&Settings::default()).is_ok_and(|shot| shot.pitch == lob.pitch.indirect_shot));
+    }
+
+    #[test]
+    fn smoke_airburst() {
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        tab.distance = "100".to_string();
+        tab.ammo_type = Ammo::smoke_shell();
+        tab.calculate(&Settings::default());
+        let ground_pitch = tab.pitch.direct_shot;
+        let ground_time = tab.time.direct_shot;
+
+        tab.burst_height = "15".to_string();
+        tab.calculate(&Settings::default());
+        let solved = tab.solved.unwrap();
+        assert_eq!(solved.height, 15.0);
+        assert_eq!(tab.target_height, 0.0);
+        assert!(tab.pitch.direct_shot > ground_pitch);
+        assert!((trajectory_height(100.0, solved.drag, solved.velocity, solved.gravity, tab.pitch.direct_shot) - 15.0).abs() < 1e-6);
+        assert!(seconds_to_ticks(tab.time.direct_shot) != seconds_to_ticks(ground_time));
+
+        //Only smoke shells burst in the air
+        tab.ammo_type = Ammo::he_shell();
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.solved.unwrap().height, 0.0);
+    }
+
+    #[test]
+    fn gravity_multiplier() {
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        tab.distance = "100".to_string();
+        tab.charges = "2".to_string();
+        tab.calculate(&Settings::default());
+        let direct = tab.pitch.direct_shot;
+        let indirect = tab.pitch.indirect_shot;
+
+        //Stronger gravity needs a higher direct shot and a lower lob
+        let heavy = Settings { gravity_multiplier: 2.0, ..Settings::default() };
+        tab.calculate(&heavy);
+        assert!(tab.pitch.direct_shot > direct);
+        assert!(tab.pitch.indirect_shot < indirect);
+        assert_eq!(tab.solved.unwrap().gravity, 20.0);
+
+        let weightless = Settings { gravity_multiplier: 0.0, ..Settings::default() };
+        tab.calculate(&weightless);
+        assert_eq!(tab.solve_error, Some(SolveError::InvalidInput(InputError::Ammo)));
+        assert!(tab.pitch.direct_shot.is_nan() && tab.solved.is_none());
+    }
+
+    #[test]
+    fn angle_units() {
+        use std::f64::consts::FRAC_PI_4;
+        assert!((AngleUnit::Degrees.to_radians(45.0) - FRAC_PI_4).abs() < 1e-12);
+        assert!((AngleUnit::Degrees.to_unit(FRAC_PI_4) - 45.0).abs() < 1e-12);
+        assert_eq!(AngleUnit::Radians.to_radians(FRAC_PI_4), FRAC_PI_4);
+        assert_eq!(AngleUnit::Radians.to_unit(FRAC_PI_4), FRAC_PI_4);
+
+        //Headings past a full turn or below zero wrap around
+        assert!((AngleUnit::Degrees.heading(405.0) - FRAC_PI_4).abs() < 1e-12);
+        assert!((AngleUnit::Degrees.heading(-90.0) - 270f64.to_radians()).abs() < 1e-12);
+        assert!((AngleUnit::Radians.heading(-FRAC_PI_4) - 7.0*FRAC_PI_4).abs() < 1e-12);
+
+        assert_eq!(AngleUnit::Degrees.format(FRAC_PI_4, 1), "45.0°");
+        assert_eq!(AngleUnit::Radians.format(FRAC_PI_4, 4), "0.7854 rad");
+        assert_eq!(AngleUnit::Degrees.format_offset(-0.5f64.to_radians(), 1), "-0.5°");
+
+        //The polar tab reads the azimuth in the chosen unit
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        tab.distance = "100".to_string();
+        tab.azimuth = "2.5".to_string();
+        tab.calculate(&Settings { angle_unit: AngleUnit::Radians, ..Settings::default() });
+        assert!((tab.yaw - 2.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn safe_flight_time() {
+        let mut settings = Settings::default();
+        assert!(!settings.exceeds_safe_flight_time(500.0));
+        settings.max_safe_flight_time = 20.0;
+        assert!(!settings.exceeds_safe_flight_time(20.0));
+        assert!(settings.exceeds_safe_flight_time(20.5));
+
+        //Older settings without the field keep it off
+        let old: Settings = serde_json::from_str(r#"{"decimals": 2}"#).unwrap();
+        assert_eq!(old.max_safe_flight_time, 0.0);
+    }
+
+    #[test]
+    fn maximum_range() {
+        //Target just under the top of the highest arc that reaches 100 blocks with one charge of shot
+        let crit = find_critical_point(100.0, 0.01, 40.0, 10.0).unwrap();
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.t_x = "100".to_string();
+        tab.t_y = format!("{}", trajectory_height(100.0, 0.01, 40.0, 10.0, crit) - 1e-9);
+        tab.charges = "1".to_string();
+        tab.calculate(&Settings::default());
+        assert!(tab.merged_shot());
+        assert_eq!(tab.shot_tip(false), Text::MergedTip);
+
+        tab.t_y = "0".to_string();
+        tab.calculate(&Settings::default());
+        assert!(!tab.merged_shot());
+        assert_eq!([tab.shot_tip(false), tab.shot_tip(true)], [Text::DirectTip, Text::IndirectTip]);
+    }
+
+    #[test]
+    fn tooltips() {
+        //The yaw tooltip describes the same convention the compass readout uses
+        for (degrees, direction) in [(0.0, "S"), (90.0, "W"), (180.0, "N"), (270.0, "E")] {
+            assert_eq!(compass_direction(f64::to_radians(degrees)), direction);
+        }
+        assert!(Text::YawTip.get(Language::English).contains("0° faces south (+Z), 90° west, 180° north, 270° east"));
+
+        let tips = [
+            Text::CannonTip, Text::TargetTip, Text::TargetOffsetTip, Text::AmmoTip, Text::ChargesTip,
+            Text::YawTip, Text::DirectTip, Text::IndirectTip, Text::MergedTip
+        ];
+        for tip in tips {
+            let [english, french, german] = [Language::English, Language::French, Language::German].map(|language| tip.get(language));
+            assert!(!english.is_empty() && english != french && english != german);
+        }
+    }
+
+    #[test]
+    fn fire_mission() {
+        let mut tab = MyTab::fire_mission(SurfaceIndex::main(), NodeIndex(1));
+        tab.c_y = "64".to_string();
+        tab.charges = "2".to_string();
+        tab.mission_targets = vec![
+            ["100".to_string(), "64".to_string(), "0".to_string()],
+            ["x".to_string(), "64".to_string(), "0".to_string()],
+            ["5000".to_string(), "64".to_string(), "0".to_string()],
+            Default::default(),
+            ["0".to_string(), "64".to_string(), "-150".to_string()]
+        ];
+        tab.calculate(&Settings::default());
+        assert!(tab.problems.is_empty());
+        assert_eq!(tab.mission.len(), 5);
+
+        //Each row matches a Cartesian tab aimed at the same target
+        let mut single = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(2));
+        single.c_y = "64".to_string();
+        single.charges = "2".to_string();
+        [single.t_x, single.t_y, single.t_z] = tab.mission_targets[4].clone();
+        single.calculate(&Settings::default());
+        let shot = tab.mission[4].as_ref().unwrap();
+        assert_eq!(shot.pitch, single.pitch.direct_shot);
+        assert_eq!(shot.yaw, single.yaw);
+        assert_eq!(shot.charges, 2);
+
+        //Bad rows fail on their own without holding up the rest
+        assert_eq!(tab.mission[1].as_ref().unwrap_err(), "Target X is not a number");
+        assert!(tab.mission[2].as_ref().unwrap_err().starts_with(Text::OutOfRange.get(Language::English)));
+        assert!(tab.mission[3].is_err());
+        assert!(tab.mission[0].is_ok());
+
+        //Lobbed rows fire the indirect arc
+        tab.preference = Preference::Lobbed;
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.mission[4].as_ref().unwrap().pitch, single.pitch.indirect_shot);
+
+        //A bad cannon stops the whole mission
+        tab.c_x = "?".to_string();
+        tab.calculate(&Settings::default());
+        assert!(!tab.problems.is_empty() && tab.mission.is_empty());
+        assert_eq!(tab.title(Language::English), "Fire Mission 1");
+    }
+
+    #[test]
+    fn ammo_search() {
+        let profiles = vec![Ammo::custom("Heavy Slug", 0.02, 10.0), Ammo::custom("Light Dart", 0.005, 10.0)];
+        let registry = Ammo::registry(&profiles);
+        assert_eq!(filter_ammo(registry.clone(), "", Language::English).len(), 8);
+        assert_eq!(filter_ammo(registry.clone(), "nothing", Language::English).len(), 6);
+
+        //Case-insensitive, the built-ins stay listed
+        let found = filter_ammo(registry, "  sLuG", Language::English);
+        assert_eq!(found.len(), 7);
+        assert_eq!(found[6].name, "Heavy Slug");
+    }
+
+    #[test]
+    fn calculation_log() {
+        assert_eq!(clock_time(0), "00:00:00");
+        assert_eq!(clock_time(86400 + 3600*13 + 60*5 + 9), "13:05:09");
+
+        //Parse problems are recorded instead of looking like an out of range target
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.log.len(), 1);
+        assert!(tab.log[0].error && tab.log[0].message.contains("Enter the distance to the target"));
+
+        tab.distance = "200".to_string();
+        tab.charges = "4".to_string();
+        tab.calculate(&Settings::default());
+        assert!(!tab.log[1].error && tab.log[1].message.starts_with("d=200.00 y=0.00 v=160.0"));
+        assert!(tab.log[1].message.contains(tab.solve_info.unwrap().method));
+
+        tab.distance = "5000".to_string();
+        tab.calculate(&Settings::default());
+        assert!(tab.log[2].error && tab.log[2].message.ends_with(&SolveError::OutOfRange.to_string()));
+
+        //Only the latest entries are kept, each distance is new so every calculation runs
+        for i in 0..LOG_LIMIT {
+            tab.distance = (5001 + i).to_string();
+            tab.calculate(&Settings::default());
+        }
+        assert_eq!(tab.log.len(), LOG_LIMIT);
+        assert!(tab.log.iter().all(|entry| entry.error));
+    }
+
+    #[test]
+    fn facing_relative() {
+        //Facing west, 50 ahead and 10 to the right is 50 blocks west and 10 north
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        tab.facing = true;
+        tab.azimuth = "90".to_string();
+        tab.forward = "50".to_string();
+        tab.right = "10".to_string();
+        tab.height = "5".to_string();
+        tab.charges = "2".to_string();
+        tab.calculate(&Settings::default());
+
+        let mut cartesian = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(2));
+        cartesian.relative = true;
+        [cartesian.t_x, cartesian.t_y, cartesian.t_z] = ["-50".to_string(), "5".to_string(), "-10".to_string()];
+        cartesian.charges = "2".to_string();
+        cartesian.calculate(&Settings::default());
+        assert!((tab.yaw - cartesian.yaw).abs() < 1e-12);
+        assert!((tab.pitch.direct_shot - cartesian.pitch.direct_shot).abs() < 1e-12);
+
+        //The distance field isn't needed in this mode, but the offsets must be numbers
+        assert!(tab.problems.is_empty());
+        tab.right = "-".to_string();
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.problems, vec!["Right: enter a number".to_string()]);
+    }
+
+    #[test]
+    fn max_range_readout() {
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        tab.distance = "100".to_string();
+        tab.charges = "2".to_string();
+        tab.calculate(&Settings::default());
+        let range: f64 = tab.max_range.unwrap();
+
+        //Stored by calculate, it follows the charges and the world gravity and needs a charge count to show anything
+        tab.charges = "4".to_string();
+        assert_eq!(tab.max_range, Some(range));
+        tab.calculate(&Settings::default());
+        assert!(tab.max_range.unwrap() > range);
+        let weightless = Settings { gravity_multiplier: 0.0, ..Settings::default() };
+        tab.calculate(&weightless);
+        assert!(tab.max_range.is_none());
+        tab.charges = "".to_string();
+        tab.calculate(&Settings::default());
+        assert!(tab.max_range.is_none());
+    }
+
+    #[test]
+    fn number_steppers() {
+        let old: Settings = serde_json::from_str(r#"{"decimals": 2}"#).unwrap();
+        assert!(!old.steppers);
+
+        //Drawing a stepper leaves the text alone until it's dragged, and it joins the focus chain like a text field
+        let mut charges = "".to_string();
+        let mut x = "-12.5".to_string();
+        let mut chain: Vec<egui::Id> = Vec::new();
+        let _ = egui::Context::default().run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                stepper_field(ui, &mut charges, 0, 1.0, &mut chain);
+                coordinate_field(ui, &mut x, true, Language::English, &mut chain);
+            });
+        });
+        assert_eq!(charges, "");
+        assert_eq!(x, "-12.5");
+        assert_eq!(chain.len(), 2);
+    }
+
+    #[test]
+    fn tab_cycling() {
+        let order = [1, 4, 2];
+        assert_eq!(cycle_tab(&order, 1, false), Some(4));
+        assert_eq!(cycle_tab(&order, 2, false), Some(1));
+        assert_eq!(cycle_tab(&order, 1, true), Some(2));
+        assert_eq!(cycle_tab(&order, 4, true), Some(1));
+        assert_eq!(cycle_tab(&[3], 3, false), None);
+        assert_eq!(cycle_tab(&order, 7, false), None);
+    }
+
+    #[test]
+    fn overview_rows() {
+        let settings = Settings::default();
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        assert_eq!(tab.overview_row(&settings), ["—", "Shot", "—", "—"].map(String::from));
+
+        tab.t_x = "200".to_string();
+        tab.t_z = "-3.5".to_string();
+        tab.charges = "4".to_string();
+        tab.calculate(&settings);
+        let row = tab.overview_row(&settings);
+        assert_eq!(row[0], "200, 0, -3.5");
+        assert_eq!(row[2], settings.angle_unit.format(tab.yaw, settings.decimals));
+        assert_eq!(row[3], settings.angle_unit.format(tab.pitch.direct_shot, settings.decimals));
+
+        //The pitch follows the preferred arc
+        tab.preference = Preference::Lobbed;
+        assert_eq!(tab.overview_row(&settings)[3], settings.angle_unit.format(tab.pitch.indirect_shot, settings.decimals));
+
+        let mut polar = MyTab::polar(SurfaceIndex::main(), NodeIndex(2));
+        polar.distance = "150".to_string();
+        assert_eq!(polar.overview_row(&settings)[0], "150 blocks away");
+        assert_eq!(MyTab::fire_mission(SurfaceIndex::main(), NodeIndex(3)).overview_row(&settings)[0], "1 target");
+    }
+
+    #[test]
+    fn aim_adjustment() {
+        let settings = Settings::default();
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.t_x = "-100".to_string();
+        tab.t_z = "5".to_string();
+        tab.charges = "4".to_string();
+        tab.calculate(&settings);
+        let pitch: f64 = tab.pitch.direct_shot;
+        assert!(tab.adjustment_text(pitch, &settings).is_none());
+
+        //Aiming at 350° with the target at ~87° is a turn right past 0°, not 263° left
+        tab.current_yaw = "350".to_string();
+        tab.current_pitch = format!("{}", pitch.to_degrees() + 2.0);
+        let yaw_delta: f64 = tab.yaw.to_degrees() + 10.0;
+        assert_eq!(
+            tab.adjustment_text(pitch, &settings).unwrap(),
+            format!("Δyaw: {:.4}° right, Δpitch: 2.0000° down", yaw_delta)
+        );
+    }
+
+    #[test]
+    fn markdown_report() {
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        [tab.c_x, tab.c_y, tab.c_z] = ["10".to_string(), "64".to_string(), "".to_string()];
+        [tab.t_x, tab.t_y, tab.t_z] = ["210".to_string(), "64".to_string(), "0".to_string()];
+        tab.ammo_type = Ammo::he_shell();
+        tab.charges = "4".to_string();
+
+        //Nothing calculated yet, the solutions are dashes and there's no trajectory
+        let report = tab.to_markdown();
+        assert!(report.starts_with("# Cartesian Tab 1\n\n## Inputs\n\n| Input | Value |\n|---|---|\n| Cannon | 10, 64, 0 |\n| Target | 210, 64, 0 |\n"));
+        assert!(report.contains("| Ammo | HE Shell |\n| Charges | 4 |\n"));
+        assert!(report.contains("| Pitch | — | — |\n"));
+        assert!(!report.contains("## Trajectory"));
+
+        tab.calculate(&Settings::default());
+        let report = tab.to_markdown();
+        assert!(report.contains(&format!("| Pitch | {:.4}° | {:.4}° |\n", tab.pitch.direct_shot.to_degrees(), tab.pitch.indirect_shot.to_degrees())));
+        assert!(report.contains(&format!("| Fuze | {} ticks | {} ticks |\n", seconds_to_ticks(tab.time.direct_shot), seconds_to_ticks(tab.time.indirect_shot))));
+        assert!(report.contains("- Distance: 200.0000 blocks\n- Height: 0.0000 blocks\n- Max range: "));
+        assert!(report.contains("## Trajectory") && report.ends_with("```\n"));
+
+        //The sketch is scaled to fit, the start in the bottom left and the far end on the right
+        let sketch = ascii_trajectory(&[(vec![[0.0, 0.0], [50.0, 10.0], [100.0, 0.0]], '*')], 11, 3);
+        assert_eq!(sketch, "     *\n\n*         *\n");
+    }
+
+    #[test]
+    fn simulation_solver() {
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        tab.distance = "200".to_string();
+        tab.charges = "2".to_string();
+        tab.calculate(&Settings::default());
+        let analytic = tab.pitch;
+        assert!(tab.analytic_pitch.direct_shot.is_nan());
+
+        //The simulated pitch is the one to aim with, the analytic one stays alongside it
+        let simulation = Settings { solver: Solver::Simulation, ..Settings::default() };
+        tab.calculate(&simulation);
+        assert_eq!(tab.analytic_pitch.direct_shot, analytic.direct_shot);
+        assert_eq!(tab.analytic_pitch.indirect_shot, analytic.indirect_shot);
+        assert!(tab.pitch.direct_shot != analytic.direct_shot && (tab.pitch.direct_shot - analytic.direct_shot).abs() < 0.01);
+        assert!(MyTab::analytic_text(tab.pitch.direct_shot, tab.analytic_pitch.direct_shot, &simulation).is_some());
+        assert_eq!(tab.solve_info.unwrap().method, "Tick simulation");
+    }
+
+    #[test]
+    fn preferred_solution() {
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        assert_eq!(tab.preferred_shot(), None);
+
+        //Flat by default, the lob once asked for
+        tab.distance = "100".to_string();
+        tab.max_elevation = "90".to_string();
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.preferred_shot(), Some(false));
+        tab.preference = Preference::Lobbed;
+        assert_eq!(tab.preferred_shot(), Some(true));
+        assert_eq!(tab.duplicate(SurfaceIndex::main(), NodeIndex(2)).preference, Preference::Lobbed);
+
+        //A lob past the mount's limit leaves the direct shot as the only one to use
+        tab.max_elevation = "60".to_string();
+        assert_eq!(tab.preferred_shot(), Some(false));
+    }
+
+    #[test]
+    fn tab_names() {
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(3));
+        assert_eq!(tab.title(Language::English), "Cartesian Tab 3");
+        tab.name = "North wall".to_string();
+        assert_eq!(tab.title(Language::German), "North wall");
+
+        //Clearing the name brings the generated title back
+        tab.name = "  ".to_string();
+        assert_eq!(tab.title(Language::English), "Cartesian Tab 3");
+
+        //A copy starts with the generated title so the two can be told apart
+        tab.name = "North wall".to_string();
+        assert!(tab.duplicate(SurfaceIndex::main(), NodeIndex(3)).name.is_empty());
+    }
+
+    #[test]
+    fn undo_history() {
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.commit_edit();
+        tab.t_x = "100".to_string();
+        tab.commit_edit();
+        tab.t_x = "250".to_string();
+        tab.commit_edit();
+
+        tab.undo();
+        assert_eq!(tab.t_x, "100");
+        tab.undo();
+        assert_eq!(tab.t_x, "");
+        //Nothing before the starting point
+        tab.undo();
+        assert_eq!(tab.t_x, "");
+        tab.redo();
+        tab.redo();
+        assert_eq!(tab.t_x, "250");
+
+        //A new edit after undoing drops the redo steps
+        tab.undo();
+        tab.t_z = "-40".to_string();
+        tab.commit_edit();
+        tab.redo();
+        assert_eq!((tab.t_x.as_str(), tab.t_z.as_str()), ("100", "-40"));
+
+        //Not just the text fields, picking another ammo or adding a row is a step too
+        tab.ammo_type = Ammo::mortar_stone();
+        tab.commit_edit();
+        tab.table_pitch = "30".to_string();
+        tab.terrain.push(["50".to_string(), "10".to_string()]);
+        tab.relative = !tab.relative;
+        tab.commit_edit();
+        tab.undo();
+        assert!(tab.ammo_type == Ammo::mortar_stone());
+        assert_eq!((tab.table_pitch.as_str(), tab.terrain.len()), ("45", 0));
+        tab.undo();
+        assert!(tab.ammo_type == Ammo::shot());
+        tab.redo();
+        tab.redo();
+        assert_eq!((tab.table_pitch.as_str(), tab.terrain.len()), ("30", 1));
+
+        //Each tab keeps its own history, capped so long sessions don't grow it forever
+        let mut other = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(2));
+        other.undo();
+        assert_eq!(other.t_x, "");
+        for i in 0..HISTORY_LIMIT + 10 {
+            tab.t_y = i.to_string();
+            tab.commit_edit();
+        }
+        assert_eq!(tab.history.undo.len(), HISTORY_LIMIT);
+    }
+
+    #[test]
+    fn duplicate_tab() {
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(3));
+        tab.distance = "120".to_string();
+        tab.azimuth = "45".to_string();
+        tab.ammo_type = Ammo::he_shell();
+        tab.charges = "2".to_string();
+        tab.max_elevation = "70".to_string();
+        tab.calculate(&Settings::default());
+        assert!(tab.pitch.direct_shot.is_finite());
+
+        let copy = tab.duplicate(SurfaceIndex::main(), NodeIndex(1));
+        assert_eq!(copy.kind, MyTabKind::Polar);
+        assert_eq!(copy.node, NodeIndex(1));
+        assert_eq!(copy.distance, "120");
+        assert_eq!(copy.azimuth, "45");
+        assert!(copy.ammo_type == Ammo::he_shell());
+        assert_eq!(copy.charges, "2");
+        assert_eq!(copy.max_elevation, "70");
+        assert!(copy.yaw.is_nan() && copy.pitch.direct_shot.is_nan() && copy.solved.is_none());
+    }
+
+    #[test]
+    fn range_table() {
+        let table = MyTab::range_table(SurfaceIndex::main(), NodeIndex(1));
+        assert_eq!(table.kind, MyTabKind::RangeTable);
+        assert_eq!(table.title(Language::English), "Range Table 1");
+    }
+
+    #[test]
+    fn coordinate_parsing() {
+        let cases = [
+            ("XYZ: 123.45 / 64.00 / -678.90", ["123.45", "64.00", "-678.90"]),
+            ("Block: 123 64 -678 [11 0 6]", ["123", "64", "-678"]),
+            ("Targeted Block: -5, 70, 12", ["-5", "70", "12"]),
+            ("1.5/2/-3.25", ["1.5", "2", "-3.25"]),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(parse_coordinates(text), Some(expected.map(String::from)), "{}", text);
+        }
+        assert_eq!(parse_coordinates(""), None);
+        assert_eq!(parse_coordinates("XYZ: 123.45 / 64.00"), None);
+        assert_eq!(parse_coordinates("hello world"), None);
+    }
+
+    #[test]
+    fn command_line() {
+        let args = |s: &str| -> Vec<String> { s.split('|').map(String::from).collect() };
+
+        let json = run_cli(&args("--cannon|0,64,0|--target|-100,64,0")).unwrap();
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.c_y = "64".to_string();
+        tab.t_x = "-100".to_string();
+        tab.t_y = "64".to_string();
+        tab.calculate(&Settings::default());
+        assert!(json.contains("\"yaw\": 90.0"));
+        assert!(json.contains(&format!("\"pitch\": {}", tab.pitch.direct_shot.to_degrees())));
+        assert!(json.contains(&format!("\"time\": {}", tab.time.indirect_shot)));
+
+        let json = run_cli(&args("--cannon|0 64 0|--target|300,64,0|--ammo|HE Shell|--charges|4")).unwrap();
+        assert!(json.contains("\"indirect\""));
+
+        assert_eq!(run_cli(&args("--cannon|0,0,0|--target|5000,0,0")), Err("OUT OF RANGE".to_string()));
+        assert!(run_cli(&args("--cannon|0,0,0")).unwrap_err().starts_with("Missing --target"));
+        assert!(run_cli(&args("--cannon|0,0,0|--target|1,0,1|--ammo|Nuke")).unwrap_err().starts_with("Unknown ammo"));
+        assert_eq!(run_cli(&args("--cannon|0 64 0|--target|300,64,0|--ammo|he_shell|--charges|4")), Ok(json));
+        assert!(run_cli(&args("--cannon|0,0,0|--target|1,0,1|--ammo|custom")).unwrap_err().starts_with("Unknown ammo"));
+        assert!(run_cli(&args("--cannon|0,0")).unwrap_err().starts_with("Invalid cannon"));
+        assert!(run_cli(&args("--target")).unwrap_err().starts_with("Missing value"));
+    }
+
+    #[test]
+    fn world_height() {
+        let settings = Settings::default();
+        assert_eq!(settings.world_height_hint("99999").unwrap(), "Y is outside the world (-64 to 320)");
+        assert!(settings.world_height_hint("-65").is_some());
+        assert!(settings.world_height_hint("-64").is_none());
+        assert!(settings.world_height_hint("320").is_none());
+
+        //Nothing to flag while the field is empty or half-typed
+        assert!(settings.world_height_hint("").is_none());
+        assert!(settings.world_height_hint("-").is_none());
+
+        let custom = Settings { world_min_y: -2032.0, build_height: 2032.0, ..Settings::default() };
+        assert!(custom.world_height_hint("1500").is_none());
+    }
+
+    #[test]
+    fn salvo_planning() {
+        //Two batteries at different distances end up with flight times closer than their first solutions
+        let position = |x: &str| [x.to_string(), "0".to_string(), "0".to_string()];
+        let mut settings = Settings::default();
+        settings.save_cannon("Near", position("-80"));
+        settings.save_cannon("Far", position("-200"));
+        settings.save_cannon("Too far", position("-9000"));
+        let mut tab = MyTab::salvo(SurfaceIndex::main(), NodeIndex(1));
+        tab.ammo_type = Ammo::he_shell();
+        tab.calculate(&settings);
+        assert_eq!(tab.salvo.len(), 3);
+        assert!(tab.salvo[2].1.is_none());
+        let near = tab.salvo[0].1.unwrap();
+        let far = tab.salvo[1].1.unwrap();
+        let first = |x: f64| salvo_options([x, 0.0, 0.0], &Ammo::he_shell(), Ammo::he_shell().gravity, DEFAULT_MIN_ELEVATION, DEFAULT_MAX_ELEVATION)[0];
+        assert!((near.time - far.time).abs() <= (first(80.0).time - first(200.0).time).abs());
+        //Both cannons sit west of the target and fire east
+        assert!((far.yaw - 3.0*TAU/4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn localization() {
+        assert_eq!(Text::Calculate.get(Language::French), "Calculer");
+        assert_eq!(Text::Calculate.get(Language::German), "Berechnen");
+
+        //Errors still print in English for the command line, the tabs show them translated
+        assert_eq!(SolveError::OutOfRange.to_string(), "OUT OF RANGE");
+        assert_eq!(error_text(SolveError::OutOfRange).get(Language::German), "AUSSER REICHWEITE");
+
+        //Built-in ammo names are translated for display only, custom names are shown as typed
+        let he = Ammo::he_shell();
+        assert_eq!(ammo_label(&he, Language::French), "Obus explosif");
+        assert_eq!(he.name, "HE Shell");
+        assert_eq!(ammo_label(&Ammo::custom("HE Shell", 0.01, 0.05), Language::French), "HE Shell");
+        let renamed = Ammo { name: "Heavy HE".to_string(), ..Ammo::he_shell() };
+        assert_eq!(ammo_label(&renamed, Language::German), "Heavy HE");
+
+        let settings = Settings { language: Language::German, ..Settings::default() };
+        assert_eq!(MyTab::pitch_text(TAU/8.0, &settings), "Höhenwinkel: 45.0000°");
+        assert_eq!(MyTab::polar(SurfaceIndex::main(), NodeIndex(2)).title(Language::French), "Onglet polaire 2");
+
+        //Result labels and warnings go through the same table
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(3));
+        tab.t_x = "250".to_string();
+        tab.charges = "2".to_string();
+        tab.step_size = "5".to_string();
+        tab.calculate(&settings);
+        assert!(tab.steps_text(tab.pitch.direct_shot, &settings).unwrap().contains(" Stufen ("));
+        assert!(tab.aim_error_text(tab.pitch.direct_shot, &settings).unwrap().starts_with("Abweichung beim Probeschuss: "));
+        let french = Settings { language: Language::French, ..Settings::default() };
+        assert!(MyTab::barrel_text(0.1, &french).unwrap().text().starts_with("Correction du fût: "));
+        assert_eq!(Text::Despawns.get(Language::French), "Le projectile disparaît avant l'impact");
+        assert_eq!(Text::ApexAboveBuildHeight.get(Language::English), "Apex is above the build height, the projectile may despawn");
+        assert_eq!(Settings::default().language, Language::English);
+
+        //So do the problem list and the menus
+        tab.t_x = "-".to_string();
+        tab.t_y = "99999".to_string();
+        tab.calculate(&settings);
+        assert_eq!(tab.problems, vec!["Ziel X: Zahl eingeben".to_string(), "Ziel: Y liegt außerhalb der Welt (-64 bis 320)".to_string()]);
+        assert_eq!(TabDensity::Roomy.label().get(Language::French), "Aéré");
+        let forward = MyTab { table_pitch: String::new(), ..MyTab::forward(SurfaceIndex::main(), NodeIndex(4)) };
+        assert_eq!(forward.landing(&french).unwrap_err(), "Saisissez une élévation valide");
+        assert_eq!(forward.fit_calibration(&settings).unwrap_err(), Text::NotEnoughShots.get(Language::German));
+    }
+
+    #[test]
+    fn cannon_bookmarks() {
+        let position = |x: &str, y: &str, z: &str| [x.to_string(), y.to_string(), z.to_string()];
+        let mut settings = Settings::default();
+        settings.save_cannon("North battery", position("100", "64", "-20"));
+        settings.save_cannon(" ", position("0", "70", "0"));
+        assert_eq!(settings.cannons[1].name, "Cannon 2");
+
+        //Saving over a name moves the cannon instead of adding another
+        settings.save_cannon("North battery", position("110", "64", "-20"));
+        assert_eq!(settings.cannons.len(), 2);
+        assert_eq!(settings.cannons[0].position, position("110", "64", "-20"));
+
+        //Older saved settings without bookmarks still load
+        let loaded: Settings = serde_json::from_str(r#"{"build_height": 256.0}"#).unwrap();
+        assert!(loaded.cannons.is_empty());
+        let saved: Settings = serde_json::from_str(&serde_json::to_string(&settings).unwrap()).unwrap();
+        assert_eq!(saved.cannons, settings.cannons);
+    }
+
+    #[test]
+    fn despawn_limit() {
+        //Normal shots are nowhere near the limit
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        tab.distance = "100".to_string();
+        tab.calculate(&Settings::default());
+        assert!(!tab.ammo_type.despawns(tab.time.direct_shot) && !tab.ammo_type.despawns(tab.time.indirect_shot));
+    }
+
+    #[test]
+    fn calculate_with_incomplete_input() {
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.calculate(&Settings::default());
+
+        tab.t_x = "-".to_string();
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.problems, vec!["Target X: enter a number".to_string()]);
+        assert!(tab.yaw.is_nan());
+
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.problems, vec!["Enter the distance to the target".to_string()]);
+        tab.distance = "100".to_string();
+        tab.charges = "".to_string();
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.problems, vec!["Enter at least 1 charge".to_string()]);
+        tab.charges = "0".to_string();
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.problems, vec!["Enter at least 1 charge".to_string()]);
+        tab.charges = "1".to_string();
+        tab.azimuth = "-90".to_string();
+        tab.calculate(&Settings::default());
+        assert!(tab.problems.is_empty());
+        assert_eq!(tab.solve_error, None);
+        assert!((tab.yaw - f64::to_radians(270.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn validation() {
+        let settings = Settings::default();
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.t_x = "abc".to_string();
+        tab.t_y = "-500".to_string();
+        tab.barrel_length = "-3".to_string();
+        tab.charges = "0".to_string();
+        tab.calculate(&settings);
+        //Every problem is listed at once, not just the first
+        assert_eq!(tab.problems.len(), 4);
+        assert!(tab.problems.contains(&"Barrel length can't be negative".to_string()));
+        assert!(tab.problems.iter().any(|problem| problem.starts_with("Target: ")));
+
+        tab.t_x = "100".to_string();
+        tab.t_y = "0".to_string();
+        tab.barrel_length = "3".to_string();
+        tab.charges = "4".to_string();
+        tab.calculate(&settings);
+        assert!(tab.problems.is_empty());
+        assert!(tab.pitch.direct_shot.is_finite());
+
+        //Relative offsets aren't checked against the world height
+        tab.relative = true;
+        tab.t_y = "-500".to_string();
+        tab.calculate(&settings);
+        assert!(tab.problems.is_empty());
+    }
+
+    #[test]
+    fn live_recalculation() {
+        let settings = Settings::default();
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        tab.distance = "100".to_string();
+        let start = Instant::now();
+
+        //Edits are ignored while live mode is off
+        assert_eq!(tab.live_update(true, start, &settings), None);
+        assert!(tab.yaw.is_nan());
+
+        //Each edit restarts the wait, the solve only happens once typing pauses
+        tab.live = true;
+        assert_eq!(tab.live_update(true, start, &settings), Some(LIVE_DELAY));
+        assert!(tab.live_update(true, start + Duration::from_millis(100), &settings).is_some());
+        assert!(tab.live_update(false, start + Duration::from_millis(200), &settings).is_some());
+        assert!(tab.pitch.direct_shot.is_nan());
+        assert_eq!(tab.live_update(false, start + Duration::from_millis(250), &settings), None);
+        assert!(tab.pitch.direct_shot.is_finite());
+        assert_eq!(tab.edited, None);
+
+        //A lone minus sign is taken as a number still being typed, it's neither solved nor flagged and the last result stays
+        let solved = tab.pitch.direct_shot;
+        tab.distance = "-".to_string();
+        tab.live_update(true, start, &settings);
+        assert_eq!(tab.live_update(false, start + LIVE_DELAY, &settings), None);
+        assert!(tab.problems.is_empty());
+        assert_eq!(tab.pitch.direct_shot, solved);
+
+        //Once it has digits it's solved as usual
+        tab.distance = "-.5".to_string();
+        tab.live_update(true, start, &settings);
+        tab.live_update(false, start + LIVE_DELAY, &settings);
+        assert!(tab.problems.is_empty() && tab.pitch.direct_shot != solved);
+    }
+
+    #[test]
+    fn partial_numbers() {
+        for partial in ["-", ".", "-.", " - "] {
+            assert!(is_partial_number(partial), "{:?}", partial);
+        }
+        for value in ["", "-5", ".5", "-.5", "5.", "0", "--"] {
+            assert!(!is_partial_number(value), "{:?}", value);
+        }
+
+        //A doubled sign is cut back to a partial number by the filter
+        let mut typed = "--5".to_string();
+        verify_signed_float_input(&mut typed);
+        assert_eq!(typed, "-");
+        assert!(is_partial_number(&typed));
+
+        //Explicitly calculating asks for the number instead of reporting it as out of range
+        let mut tab = MyTab::polar(SurfaceIndex::main(), NodeIndex(1));
+        tab.distance = "-.".to_string();
+        tab.height = ".".to_string();
+        tab.calculate(&Settings::default());
+        assert_eq!(tab.problems, vec!["Distance: enter a number".to_string(), "Height: enter a number".to_string()]);
+        assert!(tab.solve_error.is_none());
+    }
+
+    #[test]
+    fn clear_tab() {
+        let mut tab = MyTab::cartesian(SurfaceIndex::main(), NodeIndex(1));
+        tab.clear();
+        assert!(tab.c_x.is_empty() && tab.t_z.is_empty());
+
+        tab.ammo_type = Ammo::he_shell();
+        tab.t_x = "120".to_string();
+        tab.charges = "4".to_string();
+        tab.solve(120.0, 0.0, &Settings::default());
+        assert!(tab.pitch.direct_shot.is_finite());
+
+        tab.clear();
+        assert!(tab.t_x.is_empty());
+        assert_eq!(tab.charges, "1");
+        assert!(tab.pitch.direct_shot.is_nan() && tab.time.indirect_shot.is_nan());
+        assert!(tab.solved.is_none());
+        assert!(tab.ammo_type == Ammo::he_shell());
+    }
+
+    #[test]
+    fn input_filters() {
+        let filter = |f: fn(&mut String), input: &str| { let mut s = input.to_string(); f(&mut s); s };
+
+        assert_eq!(filter(verify_positive_integer_input, "12"), "12");
+        assert_eq!(filter(verify_positive_integer_input, "12abc"), "12");
+        assert_eq!(filter(verify_positive_integer_input, "007"), "");
+        assert_eq!(filter(verify_positive_integer_input, "abc"), "");
+        assert_eq!(filter(verify_positive_integer_input, "-3"), "");
+        assert_eq!(filter(verify_positive_integer_input, ""), "");
+        assert_eq!(filter(verify_positive_integer_input, "0"), "");
+
+        assert_eq!(filter(verify_signed_float_input, "-12.5"), "-12.5");
+        assert_eq!(filter(verify_signed_float_input, "3.4.5"), "3.4");
+        assert_eq!(filter(verify_signed_float_input, "--5"), "-");
+        assert_eq!(filter(verify_signed_float_input, "x1"), "");
+    }
+
+    #[test]
+    fn focus_order() {
+        let chain: Vec<egui::Id> = ["c_x", "c_y", "c_z", "t_x"].into_iter().map(egui::Id::new).collect();
+        assert_eq!(next_in_chain(&chain, chain[2], false), Some(chain[3]));
+        assert_eq!(next_in_chain(&chain, chain[2], true), Some(chain[1]));
+
+        //Past either end, or from a widget outside the chain, egui's own order takes over
+        assert_eq!(next_in_chain(&chain, chain[3], false), None);
+        assert_eq!(next_in_chain(&chain, chain[0], true), None);
+        assert_eq!(next_in_chain(&chain, egui::Id::new("v_x"), false), None);
+    }
+
+    #[test]
+    fn optional_inputs() {
+        assert_eq!(optional_f64("", 0.0), 0.0);
+        assert_eq!(optional_f64("  ", 1.5), 1.5);
+        assert_eq!(optional_f64("-12.5", 0.0), -12.5);
+        assert_eq!(optional_f64("3.", 0.0), 3.0);
+        assert!(optional_f64("-", 0.0).is_nan());
+        assert!(optional_f64(".", 0.0).is_nan());
+        assert!(optional_f64("", f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn duplicate_coordinates() {
+        assert!(has_duplicate_coordinate("120", "64", "120"));
+        assert!(has_duplicate_coordinate("-35.5", "-35.50", "8"));
+        assert!(!has_duplicate_coordinate("0", "0", "250"));
+        assert!(!has_duplicate_coordinate("", "", "250"));
+        assert!(!has_duplicate_coordinate("12", "64", "-12"));
+    }
+
+    #[test]
+    fn close_tabs() {
+        let mut app = MyApp::default();
+        for number in 2..=5 {
+            app.dock_state.push_to_focused_leaf(MyTab::polar(SurfaceIndex::main(), NodeIndex(number)));
+        }
+        app.counter = 6;
+        let numbers = |app: &MyApp| app.dock_state.iter_all_tabs().map(|(_, tab)| tab.node.0).collect::<Vec<usize>>();
+
+        app.close_tabs(CloseTabs::ToTheRight(3));
+        assert_eq!(numbers(&app), vec![1, 2, 3]);
+        app.close_tabs(CloseTabs::Others(2));
+        assert_eq!(numbers(&app), vec![2]);
+        //New tabs keep counting up so they can't share a number with the one left
+        assert_eq!(app.counter, 6);
+
+        app.close_tabs(CloseTabs::All);
+        assert_eq!(numbers(&app), vec![1]);
+        assert_eq!(app.counter, 2);
+    }
+
+    #[test]
+    fn window_geometry() {
+        //Nothing to save until the platform reports where the window is
+        let mut viewport = egui::ViewportInfo::default();
+        assert_eq!(WindowGeometry::from_viewport(&viewport), None);
+
+        viewport.outer_rect = Some(egui::Rect::from_min_size(egui::pos2(100.0, 50.0), egui::vec2(820.0, 640.0)));
+        viewport.inner_rect = Some(egui::Rect::from_min_size(egui::pos2(110.0, 80.0), egui::vec2(800.0, 600.0)));
+        let window = WindowGeometry::from_viewport(&viewport).unwrap();
+        assert_eq!(window, WindowGeometry { position: [100.0, 50.0], size: [800.0, 600.0] });
+        assert!(window.is_usable());
+        let saved = serde_json::to_string(&window).unwrap();
+        assert_eq!(serde_json::from_str::<WindowGeometry>(&saved).unwrap(), window);
+
+        viewport.minimized = Some(true);
+        assert_eq!(WindowGeometry::from_viewport(&viewport), None);
+        assert!(!WindowGeometry { position: [0.0, 0.0], size: [10.0, 10.0] }.is_usable());
+        assert!(!WindowGeometry { position: [f32::NAN, 0.0], size: DEFAULT_WINDOW_SIZE }.is_usable());
+    }
+
+    #[test]
+    fn tab_density_style() {
+        let egui_style = egui::Style::default();
+        let compact = dock_style(&egui_style, TabDensity::Compact);
+        let normal = dock_style(&egui_style, TabDensity::Normal);
+        let roomy = dock_style(&egui_style, TabDensity::Roomy);
+
+        assert_eq!(compact.tab_bar.height, 18.0);
+        assert_eq!(normal.tab_bar.height, 24.0);
+        assert_eq!(roomy.tab_bar.height, 32.0);
+        assert_eq!(compact.tab.tab_body.inner_margin, Margin::same(2.0));
+        assert_eq!(roomy.tab.tab_body.inner_margin, Margin::same(8.0));
+    }
+
+    #[test]
+    fn theme_visuals() {
+        assert!(Theme::Dark.visuals().dark_mode);
+        assert!(!Theme::Light.visuals().dark_mode);
+        assert!(Settings::default().theme == Theme::Dark);
+    }
+}